@@ -90,11 +90,11 @@ pub async fn load() -> Result<()> {
     let db = &DB_POOL.get().await.unwrap();
     let database_ids: Vec<i64> = db.query_one("SELECT array_agg(DISTINCT database_id) FROM comparison_pco_stores", &[]).await?.get(0);
     let mut stats = Vec::new();
-    let filter = Filter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
+    let filter = QueryStatFilter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
     for group in CompressedQueryStats::load(db, filter, ()).await? {
         for stat in group.decompress()? {
             stats.push(stat);
         }
     }
-    return Ok(());
+    Ok(())
 }
@@ -64,7 +64,7 @@ pub struct QueryStat {
     pub shared_blks_read: i64,
 }
 
-static DEFAULT_COLLECTED_AT: std::sync::LazyLock<SystemTime> = std::sync::LazyLock::new(|| SystemTime::now());
+static DEFAULT_COLLECTED_AT: std::sync::LazyLock<SystemTime> = std::sync::LazyLock::new(SystemTime::now);
 
 impl Default for QueryStat {
     fn default() -> Self {
@@ -136,7 +136,7 @@ pub async fn load() -> Result<Duration> {
     let db = &DB_POOL.get().await.unwrap();
     let database_ids: Vec<i64> = db.query_one("SELECT array_agg(DISTINCT database_id) FROM synthetic_pco_stores", &[]).await?.get(0);
     let mut stats = Vec::new();
-    let filter = Filter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
+    let filter = QueryStatFilter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
 
     // This assumes the stats.push() call takes negligible time.
     let start = Instant::now();
@@ -145,14 +145,14 @@ pub async fn load() -> Result<Duration> {
             stats.push(stat);
         }
     }
-    return Ok(start.elapsed());
+    Ok(start.elapsed())
 }
 
 pub async fn load_reduce() -> Result<Duration> {
     let db = &DB_POOL.get().await.unwrap();
     let database_ids: Vec<i64> = db.query_one("SELECT array_agg(DISTINCT database_id) FROM synthetic_pco_stores", &[]).await?.get(0);
     let mut stats: AHashMap<(i64, i64, i64), QueryStat> = AHashMap::new();
-    let filter = Filter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
+    let filter = QueryStatFilter::new(&database_ids, SystemTime::UNIX_EPOCH..=SystemTime::now());
 
     let start = Instant::now();
     for group in CompressedQueryStats::load(db, filter, ()).await? {
@@ -174,5 +174,5 @@ pub async fn load_reduce() -> Result<Duration> {
         }
     }
 
-    return Ok(start.elapsed());
+    Ok(start.elapsed())
 }
@@ -0,0 +1,249 @@
+use super::*;
+use quote::quote;
+use syn::{ItemStruct, Type};
+
+pub fn generate(model: &ItemStruct, args: &Arguments, timestamp_type: TimestampType) -> proc_macro2::TokenStream {
+    let name = model.ident.clone();
+    let Arguments { timestamp, timestamp_precision, group_by, sort_by, float_round, .. } = args.clone();
+
+    let mut decompress_fields = Vec::new();
+    let mut compressed_field_sizes = Vec::new();
+    let mut streamed_fields = Vec::new();
+    let mut decompressed_fields = Vec::new();
+    for field in model.fields.iter() {
+        let ident = field.ident.clone().unwrap();
+        let ty_original = field.ty.clone();
+        let mut ty = field.ty.clone();
+        let float_round = float_round.filter(|_| is_float(&ty) || is_nested_float(&ty));
+        if group_by.contains(&ident) {
+            decompressed_fields.push((ident.clone(), quote! { self.#ident.clone() }));
+        } else {
+            let is_timestamp = timestamp.as_ref().map(|t| *t == ident).unwrap_or(false);
+            let time = TimeField::detect(&ty).filter(|_| !is_timestamp);
+            let scale = FieldOptions::of(field).scale;
+            let mut streamed = false;
+            if is_timestamp || time.is_some() || scale.is_some() {
+                ty = Type::Verbatim(quote! { i64 });
+            }
+            let widened = widened_number(&ty_original);
+            if let Some((_, widened)) = &widened {
+                ty = if is_number(&ty) { Type::Verbatim(widened.clone()) } else { Type::Verbatim(quote! { Vec<#widened> }) };
+            }
+            if is_timestamp {
+                let units_per_second = timestamp_precision.units_per_second();
+                decompress_fields.push(quote! {
+                    let #ident: Vec<i64> = pco_decompress_timestamps(&self.#ident, #units_per_second)?;
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if let Some(float_round) = float_round {
+                let decompress = match float_round {
+                    FloatRound::Fixed(mult) if is_float(&ty) => quote! { pco_decompress_round(&self.#ident, #mult)? },
+                    FloatRound::Fixed(mult) => quote! { pco_decompress_nested_round(self.#ident, #mult)?.into_iter() },
+                    FloatRound::Auto if is_float(&ty) => quote! { pco_decompress_auto_round(&self.#ident)? },
+                    FloatRound::Auto => quote! { pco_decompress_nested_auto_round(self.#ident)?.into_iter() },
+                };
+                if is_float(&ty) {
+                    decompress_fields.push(quote! {
+                        let #ident: Vec<#ty> = #decompress;
+                    });
+                    compressed_field_sizes.push(quote! { Some(#ident.len()), });
+                } else {
+                    decompress_fields.push(quote! {
+                        let mut #ident: std::vec::IntoIter<#ty> = #decompress;
+                    });
+                    compressed_field_sizes.push(quote! { Some(#ident.len()), });
+                }
+            } else if let Some(time) = time {
+                let to_int = time.encode(timestamp_precision, quote! { time });
+                decompress_fields.push(quote! {
+                    let #ident: Vec<i64> = if is_zstd(&self.#ident) {
+                        let mut values = Vec::new();
+                        for time in serde_decompress::<#ty_original>(&self.#ident) {
+                            let time = time?;
+                            values.push(#to_int);
+                        }
+                        values
+                    } else if self.#ident.is_empty() {
+                        Vec::new()
+                    } else {
+                        pco::standalone::simple_decompress(&self.#ident)?
+                    };
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if let Some(scale) = scale {
+                // Values written with MessagePack before `scale` was added are read as they were written
+                decompress_fields.push(quote! {
+                    let #ident: Vec<rust_decimal::Decimal> = if is_zstd(&self.#ident) {
+                        serde_decompress(&self.#ident).collect::<anyhow::Result<_>>()?
+                    } else if self.#ident.is_empty() {
+                        Vec::new()
+                    } else {
+                        let values = pco::standalone::simple_decompress::<i64>(&self.#ident)?;
+                        values.into_iter().map(|value| rust_decimal::Decimal::new(value, #scale)).collect()
+                    };
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if is_number(&ty) {
+                decompress_fields.push(quote! {
+                    let #ident: Vec<#ty> = if self.#ident.is_empty() {
+                        Vec::new()
+                    } else {
+                        pco::standalone::simple_decompress(&self.#ident)?
+                    };
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if is_nested_number(&ty) {
+                decompress_fields.push(quote! {
+                    let mut #ident: std::vec::IntoIter<#ty> = pco_decompress_nested(self.#ident)?.into_iter();
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if is_wide_number(&ty) {
+                decompress_fields.push(quote! {
+                    let #ident: Vec<#ty> = pco_decompress_wide(&self.#ident)?;
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else if is_nested_wide_number(&ty) {
+                decompress_fields.push(quote! {
+                    let mut #ident: std::vec::IntoIter<#ty> = pco_decompress_nested_wide(self.#ident)?.into_iter();
+                });
+                compressed_field_sizes.push(quote! { Some(#ident.len()), });
+            } else {
+                let kind = quote! { (&&&Column::<#ty>(std::marker::PhantomData)).kind() };
+                let values = match args.float_round.map(FloatRound::runtime) {
+                    Some(round) => quote! { #kind.decompress_round(&self.#ident, #round)? },
+                    None => quote! { #kind.decompress(&self.#ident)? },
+                };
+                decompress_fields.push(quote! {
+                    let mut #ident: Values<'_, #ty> = #values.into();
+                });
+                compressed_field_sizes.push(quote! { #ident.known_len(), });
+                streamed_fields.push(quote! { #ident.has_next(), });
+                streamed = true;
+            }
+            let value = if is_number(&ty) || is_wide_number(&ty) {
+                quote! { #ident.get(index).cloned().unwrap_or_default() }
+            } else if streamed {
+                quote! { #ident.next().transpose()?.unwrap_or_default() }
+            } else {
+                quote! { #ident.next().unwrap_or_default() }
+            };
+            if is_timestamp {
+                let value = timestamp_precision.decode(value, timestamp_type);
+                decompressed_fields.push((ident.clone(), value));
+            } else if let Some(time) = time {
+                let value = time.decode(timestamp_precision, value);
+                decompressed_fields.push((ident.clone(), value));
+            } else if let Some((original, _)) = &widened {
+                let value = if is_number(&ty) {
+                    narrow_number(value, original)
+                } else {
+                    let narrow = narrow_number(quote! { v }, original);
+                    quote! { #value.into_iter().map(|v| #narrow).collect() }
+                };
+                decompressed_fields.push((ident.clone(), value));
+            } else {
+                decompressed_fields.push((ident.clone(), value));
+            }
+        }
+    }
+    let decompress_fields = tokens(decompress_fields);
+    let compressed_field_sizes = tokens(compressed_field_sizes);
+    let streamed_fields = tokens(streamed_fields);
+    let decompressed_fields = flatten::assemble(&args.flattened, decompressed_fields);
+    // Groups are sorted when stored, but data written before `sort_by` was added may not be
+    let sort_results = (!sort_by.is_empty()).then(|| {
+        quote! {
+            if !results.is_sorted_by(|a, b| Self::sort_order(a, b).is_le()) {
+                results.sort_by(Self::sort_order);
+            }
+        }
+    });
+
+    // With `parallel` groups are decompressed on the rayon thread pool, and otherwise on tokio's blocking thread pool.
+    // Either way the work happens off of the caller's thread, so it doesn't block the async executor.
+    let decompress_groups = if args.parallel {
+        quote! {
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            rayon::spawn(move || {
+                // A panic would otherwise abort the process, since nothing joins the spawned task
+                let groups = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    rayon::iter::ParallelIterator::collect::<anyhow::Result<Vec<Vec<#name>>>>(rayon::iter::ParallelIterator::map(
+                        rayon::iter::IntoParallelIterator::into_par_iter(groups),
+                        Self::decompress,
+                    ))
+                }));
+                let _ = sender.send(groups);
+            });
+            let groups = receiver.await?.unwrap_or_else(|panic| Err(panic_error(panic)))?;
+        }
+    } else {
+        quote! {
+            // One task per core, since tokio's blocking pool would otherwise start a thread for each group
+            let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+            let chunk_size = groups.len().div_ceil(threads);
+            let mut groups = groups.into_iter();
+            let mut tasks = Vec::new();
+            loop {
+                let chunk: Vec<Self> = groups.by_ref().take(chunk_size).collect();
+                if chunk.is_empty() {
+                    break;
+                }
+                tasks.push(tokio::task::spawn_blocking(move || chunk.into_iter().map(Self::decompress).collect::<anyhow::Result<Vec<_>>>()));
+            }
+            let mut groups: Vec<Vec<#name>> = Vec::new();
+            for task in tasks {
+                groups.extend(task.await??);
+            }
+        }
+    };
+    let decompress_all = quote! {
+        /// Decompresses many groups in parallel, returning their data points in the same order as the groups.
+        pub async fn decompress_all(groups: Vec<Self>) -> anyhow::Result<Vec<#name>> {
+            if groups.is_empty() {
+                return Ok(Vec::new());
+            }
+            #decompress_groups
+            Ok(groups.into_iter().flatten().collect())
+        }
+    };
+    let decompress_all_sorted = timestamp.as_ref().map(|timestamp| {
+        quote! {
+            #[doc=concat!(" Decompresses many groups in parallel like [decompress_all][Self::decompress_all], returning their data points sorted by `", stringify!(#timestamp), "`.")]
+            ///
+            /// Data points with the same timestamp keep the order of their groups.
+            pub async fn decompress_all_sorted(groups: Vec<Self>) -> anyhow::Result<Vec<#name>> {
+                let mut rows = Self::decompress_all(groups).await?;
+                // Each group is already sorted when `sort_by` starts with the timestamp, which the sort takes advantage of
+                let rows = tokio::task::spawn_blocking(move || {
+                    rows.sort_by(|a, b| a.#timestamp.cmp(&b.#timestamp));
+                    rows
+                });
+                Ok(rows.await?)
+            }
+        }
+    });
+
+    quote! {
+        /// Decompresses a group of data points.
+        pub fn decompress(self) -> anyhow::Result<Vec<#name>> {
+            let mut results = Vec::new();
+            #decompress_fields
+            // Fields that weren't loaded are empty, so the longest field has the number of rows in the group. MessagePack
+            // fields are deserialized as the rows are built, so they're only read ahead when no other field was loaded.
+            let len = [#compressed_field_sizes].into_iter().flatten().max().filter(|len| *len > 0);
+            let mut index = 0;
+            while len.map_or_else(|| [#streamed_fields].contains(&true), |len| index < len) {
+                let row = #name { #decompressed_fields };
+                if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
+                    results.push(row);
+                }
+                index += 1;
+            }
+            #sort_results
+            Ok(results)
+        }
+
+        #decompress_all
+        #decompress_all_sorted
+    }
+}
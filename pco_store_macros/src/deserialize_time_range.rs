@@ -0,0 +1,27 @@
+use quote::quote;
+
+/// Other time ranges are deserialized by `deserialize_time_range` in pco_store, but `time` doesn't deserialize
+/// RFC 3339 strings by default, so a wrapper type from the user's `time` crate is generated for it
+pub fn generate() -> proc_macro2::TokenStream {
+    quote! {
+        #[derive(serde::Deserialize)]
+        #[serde(crate = "::pco_store::__private::serde", transparent)]
+        struct Rfc3339(#[serde(with = "time::serde::rfc3339")] time::OffsetDateTime);
+        impl From<Rfc3339> for time::OffsetDateTime {
+            fn from(value: Rfc3339) -> Self {
+                value.0
+            }
+        }
+
+        impl Filter {
+            fn deserialize_time_range_rfc3339<'de, D>(
+                deserializer: D,
+            ) -> Result<Option<std::ops::RangeInclusive<time::OffsetDateTime>>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                deserialize_time_range::<D, time::OffsetDateTime, Rfc3339>(deserializer)
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use super::{Arguments, tokens};
+use super::{Arguments, TimeField, tokens};
 use quote::quote;
 use syn::{Ident, ItemStruct};
 
-pub fn generate(model: ItemStruct, args: Arguments, packed_name: Ident) -> proc_macro2::TokenStream {
+/// Returns the `Fields` struct definition, and its impls which are placed alongside the other generated impls
+pub fn generate(model: ItemStruct, args: Arguments, packed_name: Ident, fields_name: &Ident) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
     let name = model.ident.clone();
     let Arguments { timestamp, group_by, .. } = args;
     let mut fields = Vec::new();
@@ -17,23 +18,38 @@ pub fn generate(model: ItemStruct, args: Arguments, packed_name: Ident) -> proc_
         let name = format!("{ident}");
         let is_timestamp = timestamp.as_ref().map(|t| *t == ident).unwrap_or(false);
         fields.push(quote! { #ident: bool, });
-        if group_by.iter().any(|i| *i == ident) || is_timestamp {
+        if group_by.contains(&ident) || is_timestamp {
             required.push(quote! { #ident: true, });
         } else {
             required.push(quote! { #ident: false, });
+            let filtered = if TimeField::detect(&field.ty).is_some() {
+                quote! { filter.#ident.is_some() }
+            } else {
+                quote! { !filter.#ident.is_empty() }
+            };
             merge_filter.push(quote! {
-                (!filter.#ident.is_empty()).then(|| self.#ident = true);
+                (#filtered).then(|| self.#ident = true);
             });
         }
         select.push(quote! { self.#ident.then(|| fields.push(#name)); });
-        load.push(quote! { #ident: if self.#ident {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-        });
+        // `group_by` fields are always loaded, so their types don't need to implement `Default`
+        if group_by.contains(&ident) {
+            load.push(quote! { #ident: {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                },
+            });
+        } else {
+            load.push(quote! { #ident: if self.#ident {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+            });
+        }
         default.push(quote! { #ident: true, });
         from.push(quote! { #name => fields.#ident = true, });
     }
@@ -44,13 +60,14 @@ pub fn generate(model: ItemStruct, args: Arguments, packed_name: Ident) -> proc_
     let load = tokens(load);
     let default = tokens(default);
     let from = tokens(from);
-    quote! {
+    let fields_struct = quote! {
         #[derive(Clone, Copy, Debug, PartialEq)]
         #[doc=concat!(" Generated by pco_store to choose which fields to decompress when loading [", stringify!(#name), "]")]
-        pub struct Fields {
+        pub struct #fields_name {
             #fields
         }
-
+    };
+    let fields_impl = quote! {
         impl Fields {
             pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
                 fields.try_into().map_err(|e| anyhow::Error::msg(e))
@@ -155,5 +172,6 @@ pub fn generate(model: ItemStruct, args: Arguments, packed_name: Ident) -> proc_
                 Ok(Fields::default())
             }
         }
-    }
+    };
+    (fields_struct, fields_impl)
 }
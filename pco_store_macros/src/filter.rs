@@ -1,38 +1,49 @@
-use super::{Arguments, tokens};
-use quote::{ToTokens, quote};
-use syn::{ItemStruct, Type};
+use super::{Arguments, TimeField, TimestampType, tokens};
+use quote::quote;
+use syn::{Ident, ItemStruct, Type};
 
-pub fn generate(model: ItemStruct, args: Arguments, using_chrono: bool, timestamp_ty: &Option<Type>) -> proc_macro2::TokenStream {
+/// Returns the `Filter` struct definition, and its impl which is placed alongside the other generated impls
+pub fn generate(
+    model: ItemStruct, args: Arguments, timestamp_type: TimestampType, timestamp_ty: &Option<Type>, filter_name: &Ident,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
     let name = model.ident.clone();
-    let Arguments { timestamp, group_by, .. } = args;
+    let Arguments { timestamp, timestamp_precision, group_by, .. } = args.clone();
     let mut filter_fields = Vec::new();
     let mut filter_conditions = Vec::new();
     let mut filter_new_args = Vec::new();
     let mut filter_new_names = Vec::new();
     for field in model.fields.iter() {
         let ident = field.ident.clone().unwrap();
+        let member = args.member(&ident);
         let ty = field.ty.clone();
-        let time = ty.to_token_stream().to_string().contains("Time");
-        if time {
+        let is_timestamp = timestamp.as_ref().map(|t| *t == ident).unwrap_or(false);
+        // The `timestamp` field's type was already detected, which also allows names like `Timestamp` without a crate
+        let time =
+            if is_timestamp { Some(TimeField::Timestamp(timestamp_type)) } else { TimeField::detect(&ty).filter(|_| !group_by.contains(&ident)) };
+        if let Some(time) = time {
+            let deserialize_with = time.deserialize_with(&ty, filter_name);
+            // Only the main timestamp is required
+            let default = (!is_timestamp).then(|| quote! { #[serde(default)] });
             filter_fields.push(quote! {
-                #[serde(deserialize_with = "deserialize_time_range")]
+                #default
+                #[serde(deserialize_with = #deserialize_with)]
                 pub #ident: Option<std::ops::RangeInclusive<#ty>>,
             });
             filter_conditions.push(quote! {
-                self.#ident.as_ref().map(|t| t.contains(&row.#ident)) != Some(false)
+                self.#ident.as_ref().map(|t| t.contains(&row.#member)) != Some(false)
             });
         } else {
             filter_fields.push(quote! {
                 #[serde(default)]
-                #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+                #[serde_as(deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>")]
                 pub #ident: Vec<#ty>,
             });
             filter_conditions.push(quote! {
-                (self.#ident.is_empty() || self.#ident.contains(&row.#ident))
+                (self.#ident.is_empty() || self.#ident.contains(&row.#member))
             });
         }
-        if group_by.iter().any(|i| *i == ident) || timestamp.as_ref().map(|t| *t == ident).unwrap_or(false) {
-            if time {
+        if group_by.contains(&ident) || is_timestamp {
+            if is_timestamp {
                 filter_new_args.push(quote! { #ident: std::ops::RangeInclusive<#ty>, });
                 filter_new_names.push(quote! { #ident: Some(#ident), });
             } else {
@@ -43,25 +54,13 @@ pub fn generate(model: ItemStruct, args: Arguments, using_chrono: bool, timestam
     }
     let filter_fields = tokens(filter_fields);
     let filter_new_args = tokens(filter_new_args);
+    let filter_new_default = (filter_new_names.len() < model.fields.len()).then(|| quote! { ..Self::default() });
     let filter_new_names = tokens(filter_new_names);
     let timestamp_helpers = timestamp.map(|timestamp| {
-        let (duration_type, duration_math) = if using_chrono {
-            (quote! { chrono::Duration }, quote! { end - start })
-        } else {
-            (quote! { std::time::Duration }, quote! { end.duration_since(start)? })
-        };
-        let truncate_nanos = if using_chrono {
-            quote! {
-                Ok(chrono::DateTime::from_timestamp_micros(time.timestamp_micros()).context("out of range")?)
-            }
-        } else {
-            quote! {
-                use std::time::{UNIX_EPOCH, Duration};
-                let duration = time.duration_since(UNIX_EPOCH).context("earlier than epoch")?;
-                let micros = duration.as_secs() * 1_000_000 + (duration.subsec_nanos() / 1_000) as u64;
-                Ok(UNIX_EPOCH + Duration::from_secs(micros / 1_000_000) + Duration::from_micros(micros % 1_000_000))
-            }
-        };
+        let duration_type = timestamp_type.duration_type();
+        let duration_math = timestamp_type.duration_math();
+        let to_int = timestamp_precision.encode(quote! { time }, timestamp_type);
+        let truncate = timestamp_precision.decode(quote! { value }, timestamp_type);
         quote! {
             /// Convenience function to unwrap the timestamp range lower and upper bounds
             pub fn range_bounds(&self) -> anyhow::Result<(#timestamp_ty, #timestamp_ty)> {
@@ -86,33 +85,35 @@ pub fn generate(model: ItemStruct, args: Arguments, using_chrono: bool, timestam
                 Ok(())
             }
 
-            /// Postgres doesn't support nanosecond precision and nor does MacOS, so this
-            /// truncates nanosecond precision for timestamp comparisons
+            /// Timestamps are stored with the `timestamp_precision` given to the macro (microseconds by default),
+            /// so this truncates the time range to that precision for timestamp comparisons
             fn range_truncate(&mut self) -> anyhow::Result<()> {
                 let (start, end) = self.range_bounds()?;
-                self.#timestamp = Some(Self::truncate_nanos(start)?..=Self::truncate_nanos(end)?);
+                self.#timestamp = Some(Self::truncate(start)?..=Self::truncate(end)?);
                 Ok(())
             }
 
-            fn truncate_nanos(time: #timestamp_ty) -> anyhow::Result<#timestamp_ty> {
-                use anyhow::Context;
-                #truncate_nanos
+            fn truncate(time: #timestamp_ty) -> anyhow::Result<#timestamp_ty> {
+                let value = #to_int;
+                let time = #truncate;
+                Ok(time)
             }
         }
     });
-    quote! {
-        #[serde_with::serde_as]
-        #[derive(Debug, Default, serde::Deserialize, Clone, PartialEq)]
-        #[serde(deny_unknown_fields)]
+    let filter = quote! {
+        #[::pco_store::__private::serde_with::serde_as(crate = "::pco_store::__private::serde_with")]
+        #[derive(Debug, Default, ::pco_store::__private::serde::Deserialize, Clone, PartialEq)]
+        #[serde(crate = "::pco_store::__private::serde", deny_unknown_fields)]
         #[doc=concat!(" Generated by pco_store to specify filters when loading [", stringify!(#name), "]")]
-        pub struct Filter {
+        pub struct #filter_name {
             #filter_fields
         }
-
+    };
+    let filter_impl = quote! {
         impl Filter {
             /// Builds new filter with the required fields defined by `group_by` and `timestamp`
             pub fn new(#filter_new_args) -> Self {
-                Self { #filter_new_names ..Self::default() }
+                Self { #filter_new_names #filter_new_default }
             }
 
             fn matches(&self, row: &#name) -> bool {
@@ -121,5 +122,6 @@ pub fn generate(model: ItemStruct, args: Arguments, using_chrono: bool, timestam
 
             #timestamp_helpers
         }
-    }
+    };
+    (filter, filter_impl)
 }
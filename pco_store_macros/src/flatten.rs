@@ -0,0 +1,146 @@
+use super::{FieldOptions, Flatten};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Field, Fields, Ident, ItemStruct, Result, Type, bracketed, parse_quote};
+
+/// A `#[pco(flatten)]` field, whose struct's fields are stored as columns named `{field}_{nested}`
+#[derive(Clone)]
+pub struct Flattened {
+    pub ident: Ident,
+    pub ty: Type,
+    /// The column and nested field names
+    pub columns: Vec<(Ident, Ident)>,
+}
+
+/// Generates a macro alongside the struct that passes its fields to `__flatten_fields`. Macros and types have separate
+/// namespaces, so the macro is re-exported with the same name as the struct. Any path or `use` that names the struct
+/// then also names the macro, anywhere in the crate.
+pub fn generate(item: ItemStruct) -> Result<TokenStream> {
+    if !matches!(item.fields, Fields::Named(_)) {
+        return Err(Error::new_spanned(&item, "pco_store::flatten requires a struct with named fields"));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(&item.generics, "pco_store::flatten doesn't support generic structs"));
+    }
+    let mut fields = Vec::new();
+    for field in item.fields.iter() {
+        if FieldOptions::parse(field)?.flatten.is_some() {
+            return Err(Error::new_spanned(field, "flattened structs can't contain flattened fields"));
+        }
+        let attrs = field.attrs.iter().filter(|a| a.path().is_ident("pco"));
+        let ident = &field.ident;
+        let ty = &field.ty;
+        fields.push(quote! { #(#attrs)* #ident: #ty });
+    }
+    let mut output = item.clone();
+    for field in output.fields.iter_mut() {
+        field.attrs.retain(|a| !a.path().is_ident("pco"));
+    }
+    let ident = &item.ident;
+    let callback = Ident::new(&format!("__pco_store_flatten_{ident}"), Span::call_site());
+    Ok(quote! {
+        #output
+
+        #[doc(hidden)]
+        macro_rules! #callback {
+            ($($item:tt)*) => {
+                ::pco_store::__private::flatten_fields! { [#(#fields),*] $($item)* }
+            };
+        }
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #callback as #ident;
+    })
+}
+
+/// Calls the macro generated for the type of a `#[pco(flatten)]` field, which calls `store` again with its fields filled in.
+/// The macro has the same path as the struct, so this works wherever the struct is in scope.
+pub fn expand(args: TokenStream, model: &ItemStruct, field: &Field) -> Result<TokenStream> {
+    let Type::Path(path) = &field.ty else {
+        return Err(Error::new_spanned(&field.ty, "flatten requires a struct type"));
+    };
+    let path = &path.path;
+    if !path.segments.last().unwrap().arguments.is_none() {
+        return Err(Error::new_spanned(&field.ty, "flatten doesn't support generic structs"));
+    }
+    let ident = &field.ident;
+    Ok(quote! {
+        #path! { #ident #[::pco_store::store(#args)] #model }
+    })
+}
+
+/// The input of `__flatten_fields`: the fields of the flattened struct, the field to fill in, and the `store` struct
+pub struct Input {
+    fields: TokenStream,
+    field: Ident,
+    item: ItemStruct,
+}
+impl Parse for Input {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        bracketed!(content in input);
+        Ok(Self { fields: content.parse()?, field: input.parse()?, item: input.parse()? })
+    }
+}
+
+pub fn fill(input: Input) -> TokenStream {
+    let Input { fields, field, mut item } = input;
+    for f in item.fields.iter_mut().filter(|f| f.ident.as_ref() == Some(&field)) {
+        f.attrs.retain(|a| !a.path().is_ident("pco"));
+        f.attrs.push(parse_quote! { #[pco(flatten(#fields))] });
+    }
+    quote! { #item }
+}
+
+/// Replaces each flattened field with the fields of its struct, so the code generators store them as separate columns
+pub fn columns(original: &ItemStruct) -> Result<(ItemStruct, Vec<Flattened>)> {
+    let mut model = original.clone();
+    let mut flattened = Vec::new();
+    let mut fields = Punctuated::new();
+    for field in original.fields.iter() {
+        let Some(Flatten::Fields(nested)) = FieldOptions::of(field).flatten else {
+            fields.push(field.clone());
+            continue;
+        };
+        let parent = field.ident.clone().unwrap();
+        if nested.is_empty() {
+            return Err(Error::new_spanned(field, "flattened structs must have fields"));
+        }
+        let mut columns = Vec::new();
+        for nested in nested {
+            let nested_ident = nested.ident.clone().unwrap();
+            let column = Ident::new(&format!("{parent}_{nested_ident}"), nested_ident.span());
+            if original.fields.iter().any(|f| f.ident.as_ref() == Some(&column)) {
+                return Err(Error::new_spanned(field, format!("flattened field `{column}` conflicts with a field of the same name")));
+            }
+            columns.push((column.clone(), nested_ident));
+            fields.push(Field { ident: Some(column), vis: field.vis.clone(), ..nested });
+        }
+        flattened.push(Flattened { ident: parent, ty: field.ty.clone(), columns });
+    }
+    if let Fields::Named(named) = &mut model.fields {
+        named.named = fields;
+    }
+    Ok((model, flattened))
+}
+
+/// Builds the fields of a decompressed row, putting the values of flattened columns back into their struct
+pub fn assemble(flattened: &[Flattened], values: Vec<(Ident, TokenStream)>) -> TokenStream {
+    let mut fields = Vec::new();
+    for (ident, value) in values.iter() {
+        match flattened.iter().find(|f| f.columns.iter().any(|(column, _)| column == ident)) {
+            None => fields.push(quote! { #ident: #value, }),
+            Some(Flattened { ident: parent, ty, columns }) if columns[0].0 == *ident => {
+                let nested = columns.iter().map(|(column, nested)| {
+                    let value = &values.iter().find(|(ident, _)| ident == column).unwrap().1;
+                    quote! { #nested: #value, }
+                });
+                fields.push(quote! { #parent: #ty { #(#nested)* }, });
+            }
+            Some(_) => {}
+        }
+    }
+    quote! { #(#fields)* }
+}
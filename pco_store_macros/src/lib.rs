@@ -0,0 +1,514 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Field, Fields, Ident, ItemStruct, LitBool, LitInt, LitStr, Result, Token, Type, bracketed, parenthesized, parse_macro_input};
+
+mod decompress;
+mod deserialize_time_range;
+mod fields;
+mod filter;
+mod flatten;
+mod load;
+mod store;
+mod timestamp;
+mod trait_impl;
+
+use flatten::Flattened;
+use timestamp::{DurationType, TimeField, TimestampPrecision, TimestampType, type_path};
+
+/// How float fields are rounded before being stored as integers
+#[derive(Clone, Copy)]
+enum FloatRound {
+    /// Multiplies by 10^N, and rounds to an integer
+    Fixed(f32),
+    /// Finds the fewest decimal places that exactly represent all values in a group
+    Auto,
+}
+
+impl FloatRound {
+    /// The matching `pco_store::__private::FloatRound`, for fields that are only known to be numbers through `PcoNumeric`
+    fn runtime(self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Fixed(mult) => quote! { FloatRound::Fixed(#mult) },
+            Self::Auto => quote! { FloatRound::Auto },
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Arguments {
+    timestamp: Option<Ident>,
+    timestamp_precision: TimestampPrecision,
+    group_by: Vec<Ident>,
+    sort_by: Vec<Ident>,
+    unique_by: Vec<Ident>,
+    float_round: Option<FloatRound>,
+    max_group_rows: Option<usize>,
+    max_group_bytes: Option<usize>,
+    table_name: Option<Ident>,
+    /// Compresses and decompresses groups on the rayon thread pool, which requires the struct to be `Send` and `Sync`
+    parallel: bool,
+    /// Set after parsing, from the `#[pco(flatten)]` fields of the struct
+    flattened: Vec<Flattened>,
+}
+impl Arguments {
+    /// How a column's value is accessed on a row, which is `row.io.read_time` for a flattened `io_read_time` column
+    fn member(&self, ident: &Ident) -> proc_macro2::TokenStream {
+        for flattened in self.flattened.iter() {
+            if let Some((_, nested)) = flattened.columns.iter().find(|(column, _)| column == ident) {
+                let parent = &flattened.ident;
+                return quote! { #parent.#nested };
+            }
+        }
+        quote! { #ident }
+    }
+}
+impl Parse for Arguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut timestamp = None;
+        let mut timestamp_precision = TimestampPrecision::default();
+        let mut group_by = Vec::new();
+        let mut sort_by = Vec::new();
+        let mut unique_by = Vec::new();
+        let mut float_round = None;
+        let mut max_group_rows = None;
+        let mut max_group_bytes = None;
+        let mut table_name = None;
+        let mut parallel = false;
+        let mut seen: Vec<Ident> = Vec::new();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            // A repeated argument would otherwise silently replace the first one
+            if seen.contains(&ident) {
+                return Err(Error::new_spanned(&ident, format!("`{ident}` is set more than once")));
+            }
+            seen.push(ident.clone());
+            let _: Token![=] = input.parse()?;
+            match ident.to_string().as_str() {
+                "timestamp" => timestamp = Some(input.parse()?),
+                "timestamp_precision" => timestamp_precision = TimestampPrecision::parse(&input.parse()?)?,
+                "group_by" => {
+                    let content;
+                    bracketed!(content in input);
+                    group_by = content.parse_terminated(Ident::parse, Token![,])?.into_iter().collect();
+                }
+                "sort_by" => {
+                    let content;
+                    bracketed!(content in input);
+                    sort_by = content.parse_terminated(Ident::parse, Token![,])?.into_iter().collect();
+                }
+                "unique_by" => {
+                    let content;
+                    bracketed!(content in input);
+                    unique_by = content.parse_terminated(Ident::parse, Token![,])?.into_iter().collect();
+                }
+                "float_round" => {
+                    if input.peek(Ident) {
+                        let value: Ident = input.parse()?;
+                        if value != "auto" {
+                            return Err(Error::new(value.span(), "float_round must be a number or `auto`"));
+                        }
+                        float_round = Some(FloatRound::Auto);
+                    } else if input.peek(LitInt) {
+                        let value: LitInt = input.parse()?;
+                        let digits: u32 = value.base10_parse()?;
+                        if digits == 0 {
+                            return Err(Error::new(value.span(), "float_round must be greater than zero"));
+                        }
+                        let mult = 10i32.checked_pow(digits).ok_or_else(|| Error::new(value.span(), "float_round must be at most 9"))?;
+                        float_round = Some(FloatRound::Fixed(mult as f32));
+                    } else {
+                        return Err(input.error("float_round must be a number or `auto`"));
+                    }
+                }
+                "max_group_rows" | "max_group_bytes" => {
+                    let value: LitInt = input.parse()?;
+                    let max: usize = value.base10_parse()?;
+                    if max == 0 {
+                        return Err(Error::new(value.span(), format!("{ident} must be greater than zero")));
+                    }
+                    if ident == "max_group_rows" {
+                        max_group_rows = Some(max);
+                    } else {
+                        max_group_bytes = Some(max);
+                    }
+                }
+                "table_name" => table_name = Some(input.parse()?),
+                "parallel" => {
+                    let value: LitBool = input.parse()?;
+                    if value.value && !cfg!(feature = "rayon") {
+                        return Err(Error::new(value.span(), "the `rayon` feature of pco_store must be enabled to use parallel"));
+                    }
+                    parallel = value.value;
+                }
+                _ => return Err(Error::new(ident.span(), format!("unknown argument `{ident}`"))),
+            }
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(Self {
+            timestamp,
+            timestamp_precision,
+            group_by,
+            sort_by,
+            unique_by,
+            float_round,
+            max_group_rows,
+            max_group_bytes,
+            table_name,
+            parallel,
+            flattened: Vec::new(),
+        })
+    }
+}
+
+/// Options set with a `#[pco(...)]` attribute on a field
+#[derive(Clone, Default)]
+struct FieldOptions {
+    /// The number of decimal places stored for a `rust_decimal::Decimal` field
+    scale: Option<u32>,
+    /// The `tokio_postgres::types::Type` of a `group_by` field, for types that it can't be inferred for
+    sql_type: Option<Ident>,
+    /// Stores the fields of a nested struct as their own columns
+    flatten: Option<Flatten>,
+}
+
+/// The fields of a `#[pco(flatten)]` struct, which are only known after `#[pco_store::flatten]` passes them to the macro
+#[derive(Clone)]
+enum Flatten {
+    Pending,
+    Fields(Vec<Field>),
+}
+
+impl FieldOptions {
+    fn parse(field: &Field) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("pco")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("scale") {
+                    let value: LitInt = meta.value()?.parse()?;
+                    let scale = value.base10_parse()?;
+                    if scale > 28 {
+                        return Err(Error::new(value.span(), "scale must be at most 28"));
+                    }
+                    options.scale = Some(scale);
+                    Ok(())
+                } else if meta.path.is_ident("sql_type") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    let sql_type = value.value().to_uppercase();
+                    if sql_type.is_empty() || !sql_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                        return Err(Error::new(value.span(), "sql_type must name a tokio_postgres::types::Type, like \"INT8\""));
+                    }
+                    options.sql_type = Some(Ident::new(&sql_type, value.span()));
+                    Ok(())
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = Some(if meta.input.peek(syn::token::Paren) {
+                        let content;
+                        parenthesized!(content in meta.input);
+                        Flatten::Fields(content.parse_terminated(Field::parse_named, Token![,])?.into_iter().collect())
+                    } else {
+                        Flatten::Pending
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported pco option"))
+                }
+            })?;
+        }
+        if options.scale.is_some() {
+            if !is_decimal(&field.ty) {
+                return Err(Error::new_spanned(&field.ty, "scale is only supported for rust_decimal::Decimal fields"));
+            }
+            if !cfg!(feature = "rust_decimal") {
+                return Err(Error::new_spanned(&field.ty, "the `rust_decimal` feature of pco_store must be enabled to use scale"));
+            }
+        }
+        if options.flatten.is_some() && (options.scale.is_some() || options.sql_type.is_some()) {
+            return Err(Error::new_spanned(field, "flatten can't be combined with other options"));
+        }
+        Ok(options)
+    }
+
+    /// Options are validated when the macro starts, so this can be used by the code generators
+    fn of(field: &Field) -> Self {
+        Self::parse(field).unwrap_or_default()
+    }
+}
+
+#[proc_macro_attribute]
+pub fn store(args: TokenStream, item: TokenStream) -> TokenStream {
+    let raw_args = proc_macro2::TokenStream::from(args.clone());
+    let a = args.clone();
+    let args = parse_macro_input!(a as Arguments);
+    let Arguments { timestamp, group_by, sort_by, unique_by, table_name, .. } = args.clone();
+    let original = parse_macro_input!(item as ItemStruct);
+    if !matches!(original.fields, Fields::Named(_)) {
+        return Error::new_spanned(&original, "pco_store::store requires a struct with named fields").to_compile_error().into();
+    }
+    if !original.generics.params.is_empty() {
+        return Error::new_spanned(&original.generics, "pco_store::store doesn't support generic structs").to_compile_error().into();
+    }
+    for field in original.fields.iter() {
+        if let Err(err) = FieldOptions::parse(field) {
+            return err.to_compile_error().into();
+        }
+    }
+    // Flattened structs are expanded one at a time, by the macro that `#[pco_store::flatten]` generated for them
+    if let Some(field) = original.fields.iter().find(|f| matches!(FieldOptions::of(f).flatten, Some(Flatten::Pending))) {
+        return flatten::expand(raw_args, &original, field).unwrap_or_else(|err| err.to_compile_error()).into();
+    }
+    // The code generators see each field of a flattened struct as a separate field, named `{field}_{nested}`
+    let (model, flattened) = match flatten::columns(&original) {
+        Ok(columns) => columns,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut args = args;
+    args.flattened = flattened;
+    let timestamp_ident = timestamp.iter().cloned().collect::<Vec<_>>();
+    for (option, idents) in [("timestamp", &timestamp_ident), ("group_by", &group_by), ("sort_by", &sort_by), ("unique_by", &unique_by)] {
+        for ident in idents.iter() {
+            if !model.fields.iter().any(|f| f.ident.as_ref() == Some(ident)) {
+                return Error::new(ident.span(), format!("{option} field not found in struct")).to_compile_error().into();
+            }
+        }
+    }
+    for (i, ident) in group_by.iter().enumerate() {
+        if timestamp.as_ref() == Some(ident) {
+            return Error::new(ident.span(), "the timestamp field can't also be a group_by field").to_compile_error().into();
+        }
+        if group_by[..i].contains(ident) {
+            return Error::new(ident.span(), "group_by field is listed more than once").to_compile_error().into();
+        }
+    }
+    for ident in timestamp_ident.iter().chain(group_by.iter()) {
+        if args.flattened.iter().any(|f| f.columns.iter().any(|(column, _)| column == ident)) {
+            return Error::new(ident.span(), "timestamp and group_by can't be flattened fields").to_compile_error().into();
+        }
+    }
+    for field in model.fields.iter() {
+        if let Err(err) = FieldOptions::parse(field).and_then(|_| DurationType::check_ambiguous(&field.ty)) {
+            return err.to_compile_error().into();
+        }
+        let is_group_by = group_by.contains(field.ident.as_ref().unwrap());
+        if FieldOptions::of(field).sql_type.is_some() && !is_group_by {
+            return Error::new_spanned(field, "sql_type is only supported for group_by fields").to_compile_error().into();
+        }
+        if is_group_by
+            && type_path(&field.ty)
+                .is_some_and(|(krate, segment)| matches!(krate.as_deref(), None | Some("std" | "core")) && segment.ident == "Option")
+        {
+            let message = "group_by fields can't be Option, since NULL values never match a filter";
+            return Error::new_spanned(&field.ty, message).to_compile_error().into();
+        }
+        if is_group_by && FieldOptions::of(field).sql_type.is_none() && store::copy_type(&field.ty).is_none() {
+            let message = "unsupported group_by type, set its Postgres type with #[pco(sql_type = \"...\")]";
+            return Error::new_spanned(&field.ty, message).to_compile_error().into();
+        }
+    }
+    // `#[pco(...)]` attributes are only used by this macro, so they're removed from the output
+    let mut item = original.clone();
+    for field in item.fields.iter_mut() {
+        field.attrs.retain(|a| !a.path().is_ident("pco"));
+    }
+    let name = model.ident.clone();
+    let packed_name = Ident::new(&format!("Compressed{}s", model.ident), Span::call_site());
+    let filter_name = Ident::new(&format!("{}Filter", model.ident), Span::call_site());
+    let fields_name = Ident::new(&format!("{}Fields", model.ident), Span::call_site());
+
+    let table_name = if let Some(table_name) = table_name {
+        table_name.to_string()
+    } else {
+        let mut table_name = String::new();
+        for c in model.ident.to_string().chars() {
+            if c.is_uppercase() && !table_name.is_empty() {
+                table_name += "_";
+            }
+            table_name += &c.to_lowercase().to_string();
+        }
+        table_name += "s";
+        table_name
+    };
+
+    let mut packed_fields = vec![quote! { filter: Option<#filter_name>, }];
+    let mut timestamp_ty = None;
+    let mut timestamp_type = TimestampType::default();
+    for field in model.fields.iter() {
+        let ident = field.ident.clone().unwrap();
+        let ty = field.ty.clone();
+        if group_by.contains(&ident) {
+            packed_fields.push(quote! { #ident: #ty, });
+        } else if timestamp.as_ref().map(|t| *t == ident).unwrap_or(false) {
+            timestamp_type = match TimestampType::detect(&ty) {
+                Ok(timestamp_type) => timestamp_type,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            timestamp_ty = Some(ty.clone());
+            packed_fields.push(quote! { #ident: Vec<u8>, });
+        } else {
+            packed_fields.push(quote! { #ident: Vec<u8>, });
+        }
+    }
+    let packed_fields = tokens(packed_fields);
+
+    let (filter, filter_impl) = filter::generate(model.clone(), args.clone(), timestamp_type, &timestamp_ty, &filter_name);
+    let (fields, fields_impl) = fields::generate(model.clone(), args.clone(), packed_name.clone(), &fields_name);
+    let time_fields: Vec<_> =
+        model.fields.iter().filter(|f| !group_by.contains(f.ident.as_ref().unwrap())).filter_map(|f| TimeField::detect(&f.ty)).collect();
+    let deserialize_time_range = (timestamp_type == TimestampType::Time || time_fields.contains(&TimeField::Timestamp(TimestampType::Time)))
+        .then(deserialize_time_range::generate);
+
+    let load_and_delete = load::generate(&model, &timestamp, timestamp_type, &group_by, &packed_name, &table_name, !unique_by.is_empty());
+    let decompress = decompress::generate(&model, &args, timestamp_type);
+    let store_and_store_grouped = store::generate(&model, &args, &table_name, timestamp_type);
+    let trait_impl = trait_impl::generate(&model, &args, &packed_name, &table_name);
+
+    let rayon = args.parallel.then(|| quote! { use ::pco_store::__private::rayon; });
+    // Only used by structs with a `scale` field
+    let rust_decimal = cfg!(feature = "rust_decimal").then(|| quote! { #[allow(unused_imports)] use ::pco_store::__private::rust_decimal; });
+
+    quote! {
+        #item
+
+        #[doc=concat!(" Generated by pco_store to store and load compressed versions of [", stringify!(#name), "]")]
+        pub struct #packed_name {
+            #packed_fields
+        }
+
+        #filter
+        #fields
+
+        // Everything else is scoped to this block, so several stores can be defined in the same module
+        const _: () = {
+            // Dependencies are imported by name, so they take precedence over any with the same name in the user's scope
+            #[allow(unused_imports)]
+            use ::pco_store::__private::{
+                ahash, anyhow, deadpool_postgres, futures, pco, rmp_serde, serde, serde_bytes, serde_with, tokio, tokio_postgres, zstd,
+            };
+            #rayon
+            #rust_decimal
+            use ::pco_store::__private::*;
+
+            type Filter = #filter_name;
+            type Fields = #fields_name;
+
+            impl #packed_name {
+                #load_and_delete
+
+                #decompress
+
+                #store_and_store_grouped
+            }
+
+            #trait_impl
+            #filter_impl
+            #fields_impl
+            #deserialize_time_range
+        };
+    }
+    .into()
+}
+
+/// Allows a struct to be used by a `#[pco(flatten)]` field of a [store] struct in the same crate, so that each of its
+/// fields are stored as their own compressed column.
+#[proc_macro_attribute]
+pub fn flatten(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return Error::new_spanned(proc_macro2::TokenStream::from(args), "pco_store::flatten doesn't take arguments").to_compile_error().into();
+    }
+    let item = parse_macro_input!(item as ItemStruct);
+    flatten::generate(item).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Called by the macro generated by [flatten] with the fields of the flattened struct, to pass them to [store]
+#[doc(hidden)]
+#[proc_macro]
+pub fn __flatten_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as flatten::Input);
+    flatten::fill(input).into()
+}
+
+fn is_number(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    matches!(ty.as_str(), "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f16" | "half :: f16" | "f32" | "f64" | "bool")
+}
+
+fn is_nested_number(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    // Remove syn's added spacing, turning "Vec < i32 >" into "Vec<i32>"
+    let ty = ty.replace(" < ", "<").replace(" >", ">").replace(" :: ", "::");
+    matches!(
+        ty.as_str(),
+        "Vec<u8>"
+            | "Vec<u16>"
+            | "Vec<u32>"
+            | "Vec<u64>"
+            | "Vec<i8>"
+            | "Vec<i16>"
+            | "Vec<i32>"
+            | "Vec<i64>"
+            | "Vec<f16>"
+            | "Vec<half::f16>"
+            | "Vec<f32>"
+            | "Vec<f64>"
+            | "Vec<bool>"
+    )
+}
+
+fn is_nested_float(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    let ty = ty.replace(" < ", "<").replace(" >", ">");
+    matches!(ty.as_str(), "Vec<f32>" | "Vec<f64>")
+}
+
+/// pco doesn't compress 8-bit types by default, so `u8`, `i8` and `bool` are stored as 16-bit integers.
+/// Returns the original and stored element types, for both single values and `Vec`s.
+fn widened_number(ty: &Type) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let ty = quote! { #ty }.to_string();
+    let ty = ty.replace(" < ", "<").replace(" >", ">");
+    match ty.as_str() {
+        "u8" | "Vec<u8>" => Some((quote! { u8 }, quote! { u16 })),
+        "i8" | "Vec<i8>" => Some((quote! { i8 }, quote! { i16 })),
+        "bool" | "Vec<bool>" => Some((quote! { bool }, quote! { u16 })),
+        _ => None,
+    }
+}
+
+/// Converts a widened value back into its original type
+fn narrow_number(value: proc_macro2::TokenStream, original: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if original.to_string() == "bool" {
+        quote! { #value == 1 }
+    } else {
+        quote! { #value as #original }
+    }
+}
+
+/// pco doesn't support 128-bit integers, so they're split into two 64-bit columns
+fn is_wide_number(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    matches!(ty.as_str(), "i128" | "u128")
+}
+
+fn is_nested_wide_number(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    let ty = ty.replace(" < ", "<").replace(" >", ">");
+    matches!(ty.as_str(), "Vec<i128>" | "Vec<u128>")
+}
+
+/// Only `f32` and `f64` are rounded by `float_round`, since `f16` can't be cast to an integer
+fn is_float(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    matches!(ty.as_str(), "f32" | "f64")
+}
+
+fn is_decimal(ty: &Type) -> bool {
+    let ty = quote! { #ty }.to_string();
+    matches!(ty.as_str(), "Decimal" | "rust_decimal :: Decimal")
+}
+
+fn tokens(input: Vec<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    let mut tokens = proc_macro2::TokenStream::new();
+    tokens.extend(input);
+    tokens
+}
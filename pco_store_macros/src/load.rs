@@ -1,10 +1,11 @@
 use quote::quote;
 use syn::{Ident, ItemStruct};
 
-use super::tokens;
+use super::{TimestampType, tokens};
 
 pub fn generate(
-    model: &ItemStruct, timestamp: &Option<Ident>, group_by: &Vec<Ident>, packed_name: &Ident, table_name: &String,
+    model: &ItemStruct, timestamp: &Option<Ident>, timestamp_type: TimestampType, group_by: &[Ident], packed_name: &Ident, table_name: &String,
+    write_order: bool,
 ) -> proc_macro2::TokenStream {
     // load and delete
     let mut load_checks = Vec::new();
@@ -14,7 +15,7 @@ pub fn generate(
     for field in model.fields.iter() {
         let ident = field.ident.clone().unwrap();
         let name = format!("{ident}");
-        if group_by.iter().any(|i| *i == ident) {
+        if group_by.contains(&ident) {
             load_checks.push(quote! {
                 if filter.#ident.is_empty() {
                     return Err(anyhow::Error::msg(#name.to_string() + " is required"));
@@ -34,15 +35,56 @@ pub fn generate(
             bind += 1;
             load_where.push(format!("start_at <= ${bind}"));
             bind += 1;
-            load_params.push(quote! {
-                filter.#ident.as_ref().unwrap().start(),
-                filter.#ident.as_ref().unwrap().end(),
-            });
+            let start = quote! { filter.#ident.as_ref().unwrap().start() };
+            let end = quote! { filter.#ident.as_ref().unwrap().end() };
+            match (timestamp_type.sql_value(quote! { (*#start) }), timestamp_type.sql_value(quote! { (*#end) })) {
+                (Some(start), Some(end)) => load_params.push(quote! { &#start, &#end, }),
+                _ => load_params.push(quote! { #start, #end, }),
+            }
         }
     }
     let load_checks = tokens(load_checks);
     let load_where = if load_where.is_empty() { "true".to_string() } else { load_where.join(" AND ") };
     let load_params = tokens(load_params);
+    // With `unique_by`, deleted groups are returned in the order they were written so that rewriting them keeps the latest rows.
+    // `write_seq` is an identity column that's assigned by Postgres as each group is written.
+    let delete_sql = if write_order {
+        quote! {
+            "WITH deleted AS (DELETE FROM ".to_string() + #table_name + " WHERE " + #load_where + " RETURNING " + fields.select().as_str()
+                + ", write_seq) SELECT " + fields.select().as_str() + " FROM deleted ORDER BY write_seq"
+        }
+    } else {
+        quote! { "DELETE FROM ".to_string() + #table_name + " WHERE " + #load_where + " RETURNING " + fields.select().as_str() }
+    };
+    let prepare = if write_order {
+        quote! { db.prepare_cached(&sql).await.map_err(|err| write_seq_error(err, #table_name))? }
+    } else {
+        quote! { db.prepare_cached(&sql).await? }
+    };
+    let delete_body = quote! {
+        let mut fields = fields.try_into().map_err(|_| anyhow::Error::msg("unknown field"))?;
+        fields.merge_filter(&filter);
+        #load_checks
+        let sql = #delete_sql;
+        let mut results = Vec::new();
+        for row in db.query(&#prepare, &[#load_params]).await? {
+            results.push(fields.load_from_row(row, None)?);
+        }
+        Ok(results)
+    };
+    // `rewrite` deletes groups inside of its transaction
+    let delete_in_transaction = write_order.then(|| {
+        quote! {
+            /// Deletes data for the specified filters as part of a transaction, returning it in the order it was written.
+            async fn delete_in_transaction(
+                db: &deadpool_postgres::Transaction<'_>,
+                mut filter: Filter,
+                fields: impl TryInto<Fields>
+            ) -> anyhow::Result<Vec<#packed_name>> {
+                #delete_body
+            }
+        }
+    });
 
     quote! {
         /// Loads data for the specified filters.
@@ -70,15 +112,9 @@ pub fn generate(
             mut filter: Filter,
             fields: impl TryInto<Fields>
         ) -> anyhow::Result<Vec<#packed_name>> {
-            let mut fields = fields.try_into().map_err(|_| anyhow::Error::msg("unknown field"))?;
-            fields.merge_filter(&filter);
-            #load_checks
-            let sql = "DELETE FROM ".to_string() + #table_name + " WHERE " + #load_where + " RETURNING " + fields.select().as_str();
-            let mut results = Vec::new();
-            for row in db.query(&db.prepare_cached(&sql).await?, &[#load_params]).await? {
-                results.push(fields.load_from_row(row, None)?);
-            }
-            Ok(results)
+            #delete_body
         }
+
+        #delete_in_transaction
     }
 }
@@ -0,0 +1,596 @@
+use super::*;
+use proc_macro2::Span;
+use quote::{ToTokens, quote};
+use syn::{Ident, ItemStruct, PathArguments, Type};
+
+pub fn generate(model: &ItemStruct, args: &Arguments, table_name: &str, timestamp_type: TimestampType) -> proc_macro2::TokenStream {
+    let name = model.ident.clone();
+    let Arguments { timestamp, timestamp_precision, group_by, sort_by, unique_by, float_round, max_group_rows, max_group_bytes, .. } = args.clone();
+
+    let mut store_fields = Vec::new();
+    let mut store_types = Vec::new();
+    let mut store_group = Vec::new();
+    let mut store_values = Vec::new();
+    let mut store_compressed = Vec::new();
+    for field in model.fields.iter() {
+        let compressed_index = store_compressed.len();
+        let ident = field.ident.clone().unwrap();
+        let member = args.member(&ident);
+        let ty_original = field.ty.clone();
+        let mut ty = field.ty.clone();
+        let float_round = float_round.filter(|_| is_float(&ty) || is_nested_float(&ty));
+        let widened = widened_number(&ty_original).map(|(_, widened)| widened);
+        if let Some(widened) = widened.clone().filter(|_| is_number(&ty)) {
+            ty = Type::Verbatim(widened);
+        }
+        if group_by.contains(&ident) {
+            store_fields.push(ident.to_string());
+            // Validated when the macro starts
+            let sql_type = FieldOptions::of(field).sql_type;
+            store_types.push(sql_type.unwrap_or_else(|| Ident::new(copy_type(&ty_original).unwrap(), Span::call_site())));
+            store_group.push(quote! { row.#ident.clone(), });
+            store_values.push(quote! { &rows[0].#ident, });
+        } else if timestamp.as_ref().map(|t| *t == ident).unwrap_or(false) {
+            store_fields.push("start_at".to_string());
+            store_fields.push("end_at".to_string());
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("TIMESTAMPTZ", Span::call_site()));
+            store_types.push(Ident::new("TIMESTAMPTZ", Span::call_site()));
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            store_values.push(quote! { &start_at, &end_at, });
+            store_compressed.push(quote! {
+                pco::standalone::simple_compress(&#timestamp, &pco::ChunkConfig::default())?
+            });
+        } else if let Some(float_round) = float_round {
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            store_compressed.push(match (float_round, is_float(&ty)) {
+                (FloatRound::Fixed(mult), true) => quote! { pco_compress_round(rows.iter().map(|r| r.#member).collect(), #mult)? },
+                (FloatRound::Fixed(mult), false) => {
+                    quote! { pco_compress_nested_round(rows.iter().map(|r| r.#member.clone()).collect(), #mult)? }
+                }
+                (FloatRound::Auto, true) => quote! { pco_compress_auto_round(rows.iter().map(|r| r.#member).collect())? },
+                (FloatRound::Auto, false) => quote! { pco_compress_nested_auto_round(rows.iter().map(|r| r.#member.clone()).collect())? },
+            });
+        } else if let Some(scale) = FieldOptions::of(field).scale {
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            let error = format!("{ident} can't be stored with scale {scale}: ");
+            store_compressed.push(quote! {
+                {
+                    let mut values: Vec<i64> = Vec::with_capacity(rows.len());
+                    for r in rows.iter() {
+                        // Midpoints round away from zero like `float_round`, instead of `round_dp`'s banker's rounding
+                        let mut value = r.#member.round_dp_with_strategy(#scale, rust_decimal::RoundingStrategy::MidpointAwayFromZero);
+                        value.rescale(#scale);
+                        let mantissa = Some(value.mantissa()).filter(|_| value.scale() == #scale).and_then(|m| i64::try_from(m).ok());
+                        values.push(mantissa.ok_or_else(|| anyhow::Error::msg(#error.to_string() + &r.#member.to_string()))?);
+                    }
+                    pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?
+                }
+            });
+        } else if let Some(time) = TimeField::detect(&ty) {
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            let to_int = time.encode(timestamp_precision, quote! { r.#member });
+            store_compressed.push(quote! {
+                {
+                    let mut values: Vec<i64> = Vec::with_capacity(rows.len());
+                    for r in rows.iter() {
+                        values.push(#to_int);
+                    }
+                    pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?
+                }
+            });
+        } else if is_wide_number(&ty) || is_nested_wide_number(&ty) {
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            if is_wide_number(&ty) {
+                store_compressed.push(quote! {
+                    pco_compress_wide(rows.iter().map(|r| r.#member).collect::<Vec<_>>())?
+                });
+            } else {
+                store_compressed.push(quote! {
+                    pco_compress_nested_wide(rows.iter().map(|r| r.#member.clone()).collect::<Vec<_>>())?
+                });
+            }
+        } else if is_number(&ty) || is_nested_number(&ty) {
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            let val = if is_number(&ty) {
+                quote! { r.#member }
+            } else {
+                quote! { v } // Closure argument inside of nested `map`
+            };
+            let expr = if let Some(widened) = &widened {
+                quote! { #val as #widened }
+            } else {
+                quote! { #val }
+            };
+            if is_number(&ty) {
+                store_compressed.push(quote! {
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| #expr).collect::<Vec<_>>(), &pco::ChunkConfig::default()
+                    )?
+                });
+            } else {
+                store_compressed.push(quote! {
+                    pco_compress_nested(
+                        rows.iter().map(|r| r.#member.iter().map(|v| *#expr).collect::<Vec<_>>()).collect::<Vec<_>>()
+                    )?
+                });
+            }
+        } else {
+            // Other types are only known to be numbers if they implement `PcoNumeric`, and otherwise use MessagePack.
+            // `float_round` is passed along for type aliases and newtypes of floats.
+            store_fields.push(ident.to_string());
+            store_types.push(Ident::new("BYTEA", Span::call_site()));
+            let kind = quote! { (&&&Column::<#ty>(std::marker::PhantomData)).kind() };
+            store_compressed.push(match args.float_round.map(FloatRound::runtime) {
+                Some(round) => quote! { #kind.compress_round(rows.iter().map(|r| &r.#member), #round)? },
+                None => quote! { #kind.compress(rows.iter().map(|r| &r.#member))? },
+            });
+        }
+        if store_compressed.len() > compressed_index {
+            store_values.push(quote! { &compressed[#compressed_index], });
+        }
+    }
+    let store_fields = store_fields.join(", ");
+    let store_types = tokens(store_types.into_iter().map(|t| quote! { tokio_postgres::types::Type::#t, }).collect());
+    let store_group = tokens(store_group);
+    let store_values = tokens(store_values);
+    // The timestamp range is returned from `compress_group` as integers, and decoded when the group is written
+    let (timestamp_collect, timestamp_bounds) = if timestamp.is_some() {
+        let to_int = timestamp_precision.encode(quote! { s.#timestamp }, timestamp_type);
+        let start_at = timestamp_precision.decode(quote! { bounds.0 }, timestamp_type);
+        let end_at = timestamp_precision.decode(quote! { bounds.1 }, timestamp_type);
+        let start_at = timestamp_type.sql_value(start_at.clone()).unwrap_or(start_at);
+        let end_at = timestamp_type.sql_value(end_at.clone()).unwrap_or(end_at);
+        let timestamp_collect = quote! {
+            let mut #timestamp: Vec<i64> = Vec::with_capacity(rows.len());
+            for s in rows.iter() {
+                #timestamp.push(#to_int);
+            }
+            let bounds = (*#timestamp.iter().min().unwrap(), *#timestamp.iter().max().unwrap());
+        };
+        let timestamp_bounds = quote! {
+            let start_at = #start_at;
+            let end_at = #end_at;
+        };
+        (timestamp_collect, timestamp_bounds)
+    } else {
+        (quote! { let bounds = (0, 0); }, quote! {})
+    };
+    let store_sql = format!("COPY {table_name} ({store_fields}) FROM STDIN BINARY");
+    let (sort_rows, sort_order) = if sort_by.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        let comparisons = sort_by.iter().map(|ident| {
+            let ty = &model.fields.iter().find(|f| f.ident.as_ref() == Some(ident)).unwrap().ty;
+            let ident = args.member(ident);
+            // Floats use `total_cmp` so NaN values still produce a total order. Other types go through `PcoNumeric`
+            // when they implement it, so type aliases and newtypes of floats are ordered the same way.
+            if is_float(ty) {
+                quote! { .then_with(|| a.#ident.total_cmp(&b.#ident)) }
+            } else {
+                quote! { .then_with(|| (&&&Column::<#ty>(std::marker::PhantomData)).kind().compare(&a.#ident, &b.#ident)) }
+            }
+        });
+        let sort_order = quote! {
+            /// The order of rows within a group, set with `sort_by`.
+            fn sort_order(a: &#name, b: &#name) -> std::cmp::Ordering {
+                std::cmp::Ordering::Equal #(#comparisons)*
+            }
+        };
+        (quote! { let mut rows = rows; rows.sort_by(Self::sort_order); }, sort_order)
+    };
+
+    let (deduplicate_rows, deduplicate) = if unique_by.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        let key: Vec<_> = group_by.iter().chain(unique_by.iter().filter(|i| !group_by.contains(i))).map(|i| args.member(i)).collect();
+        let deduplicate = quote! {
+            /// Removes rows with the same `group_by` and `unique_by` values, keeping the last one.
+            fn deduplicate(mut rows: Vec<#name>) -> Vec<#name> {
+                let mut seen = ahash::AHashSet::new();
+                rows.reverse();
+                rows.retain(|row| seen.insert((#(row.#key.clone(),)*)));
+                rows.reverse();
+                rows
+            }
+        };
+        (quote! { let rows = Self::deduplicate(rows.into_iter().collect()); }, deduplicate)
+    };
+
+    // With `parallel`, groups and the fields within each group are compressed in parallel on the rayon thread pool,
+    // so the CPU-heavy work doesn't block the async executor.
+    let (compress_fields, compress_parts) = if args.parallel {
+        let tasks = store_compressed.iter().map(|expr| {
+            quote! {
+                Box::new(|| {
+                    let compressed = #expr;
+                    Ok(compressed)
+                }),
+            }
+        });
+        let compress_fields = quote! {
+            let compressed = {
+                let tasks: Vec<Box<dyn Fn() -> anyhow::Result<Vec<u8>> + Send + Sync + '_>> = vec![#(#tasks)*];
+                rayon::iter::ParallelIterator::collect::<anyhow::Result<Vec<_>>>(rayon::iter::ParallelIterator::map(
+                    rayon::iter::IntoParallelRefIterator::par_iter(&tasks),
+                    |task| task(),
+                ))?
+            };
+        };
+        let compress_parts = quote! {
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            rayon::spawn(move || {
+                // A panic would otherwise abort the process, since nothing joins the spawned task. The rows are
+                // dropped along with the panic, so they can't be observed in a broken state.
+                let compressed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    rayon::iter::ParallelIterator::collect(rayon::iter::ParallelIterator::map(
+                        rayon::iter::IntoParallelIterator::into_par_iter(parts),
+                        Self::compress_group,
+                    ))
+                }));
+                let _ = sender.send(compressed);
+            });
+            receiver.await?.unwrap_or_else(|panic| Err(panic_error(panic)))
+        };
+        (compress_fields, compress_parts)
+    } else {
+        let compress_fields = quote! {
+            let compressed: Vec<Vec<u8>> = vec![#(#store_compressed,)*];
+        };
+        let compress_parts = quote! {
+            parts.into_iter().map(Self::compress_group).collect()
+        };
+        (compress_fields, compress_parts)
+    };
+    let compress_group = quote! {
+        /// Compresses the fields of a group, returning the rows along with their timestamp range.
+        fn compress_group(rows: Vec<#name>) -> anyhow::Result<(Vec<#name>, (i64, i64), Vec<Vec<u8>>)> {
+            #sort_rows
+            #timestamp_collect
+            #compress_fields
+            Ok((rows, bounds, compressed))
+        }
+
+        /// Compresses each part of the data that will be written as a row.
+        async fn compress_parts(parts: Vec<Vec<#name>>) -> anyhow::Result<Vec<(Vec<#name>, (i64, i64), Vec<Vec<u8>>)>> {
+            #compress_parts
+        }
+    };
+    let (split_groups, split_by_bytes, split_rows) = if max_group_rows.is_none() && max_group_bytes.is_none() {
+        (quote! { groups.collect() }, None, quote! {})
+    } else {
+        // Groups are split in timestamp order, so each part has tight `start_at` and `end_at` bounds
+        let timestamp_order = timestamp.as_ref().map(|timestamp| {
+            let to_int = timestamp_precision.encode(quote! { row.#timestamp }, timestamp_type);
+            quote! {
+                let mut keyed = Vec::with_capacity(rows.len());
+                for row in rows {
+                    keyed.push((#to_int, row));
+                }
+                keyed.sort_by_key(|(timestamp, _)| *timestamp);
+                let rows: Vec<#name> = keyed.into_iter().map(|(_, row)| row).collect();
+            }
+        });
+        let split_by_rows = max_group_rows.map(|max| {
+            quote! {
+                let mut rows = rows;
+                while rows.len() > #max {
+                    let rest = rows.split_off(#max);
+                    parts.push(rows);
+                    rows = rest;
+                }
+            }
+        });
+        // Parts that compress larger than `max_group_bytes` are halved until they fit. `split_rows` is called
+        // again first, since `sort_by` may have changed the order of the rows.
+        let split_by_bytes = max_group_bytes.map(|max| {
+            quote! {
+                if rows.len() > 1 && compressed.iter().map(|c| c.len()).sum::<usize>() > #max {
+                    let mut rows = Self::split_rows(rows)?.into_iter().flatten().collect::<Vec<_>>();
+                    let rest = rows.split_off(rows.len() / 2);
+                    parts.push(rows);
+                    parts.push(rest);
+                    continue;
+                }
+            }
+        });
+        let split_rows = quote! {
+            /// Orders a group by timestamp, and splits it into parts that fit within `max_group_rows`.
+            fn split_rows(rows: Vec<#name>) -> anyhow::Result<Vec<Vec<#name>>> {
+                #timestamp_order
+                let mut parts = Vec::new();
+                #split_by_rows
+                parts.push(rows);
+                Ok(parts)
+            }
+        };
+        let split_groups = quote! {
+            let mut parts = Vec::new();
+            for rows in groups {
+                parts.extend(Self::split_rows(rows)?);
+            }
+            parts
+        };
+        (split_groups, split_by_bytes, split_rows)
+    };
+    let write_groups = quote! {
+        let mut parts: Vec<Vec<#name>> = { #split_groups };
+        while !parts.is_empty() {
+            for (rows, bounds, compressed) in Self::compress_parts(std::mem::take(&mut parts)).await? {
+                #split_by_bytes
+                #timestamp_bounds
+                writer.as_mut().write(&[#store_values]).await?;
+            }
+        }
+    };
+
+    let store_merge = timestamp.as_ref().map(|timestamp| {
+        let to_int = timestamp_precision.encode(quote! { row.#timestamp }, timestamp_type);
+        let nanos_per_unit = timestamp_precision.nanos_per_unit();
+        let group_where: Vec<_> = group_by.iter().enumerate().map(|(i, ident)| format!("{ident} = ${}", i + 1)).collect();
+        let group_params = group_by.iter().map(|ident| quote! { &rows[0].#ident, });
+        let group_params = quote! { #(#group_params)* };
+        let bind = group_by.len() + 1;
+        // With `unique_by`, existing groups are merged in the order they were written so the latest rows are kept
+        let order_by = if unique_by.is_empty() { "" } else { " ORDER BY write_seq" };
+        let prepare_select = if unique_by.is_empty() {
+            quote! { transaction.prepare_cached(&select_sql).await? }
+        } else {
+            quote! { transaction.prepare_cached(&select_sql).await.map_err(|err| write_seq_error(err, #table_name))? }
+        };
+        let select_sql = format!(
+            "SELECT {{}}, ctid::text FROM {table_name} WHERE {} start_at >= ${bind} AND end_at <= ${}{order_by} FOR UPDATE",
+            group_where.iter().map(|w| format!("{w} AND")).collect::<Vec<_>>().join(" "),
+            bind + 1,
+        );
+        let delete_sql = format!(
+            "DELETE FROM {table_name} WHERE {} ctid = ANY(${bind}::text[]::tid[])",
+            group_where.iter().map(|w| format!("{w} AND")).collect::<Vec<_>>().join(" "),
+        );
+        quote! {
+            /// Merges the data into the existing groups for each `group_by` value and `bucket` of time, in a single transaction.
+            ///
+            /// Existing groups that fall entirely within a bucket are locked with `SELECT ... FOR UPDATE`, decompressed,
+            /// combined with the new rows and written back as one group. This keeps low-volume data compacted without
+            /// a separate rewrite job. This runs its own transaction, so it can't be called inside of another one. The
+            /// transaction is rolled back if it fails, or if the returned future is dropped before it completes.
+            pub async fn store_merge(
+                db: &mut deadpool_postgres::ClientWrapper,
+                rows: Vec<#name>,
+                bucket: std::time::Duration,
+            ) -> anyhow::Result<()> {
+                if rows.is_empty() {
+                    return Ok(());
+                }
+                let units = i64::try_from(bucket.as_nanos() as i128 / #nanos_per_unit).unwrap_or(i64::MAX);
+                if units == 0 {
+                    return Err(anyhow::Error::msg("bucket must be at least one timestamp_precision unit"));
+                }
+                let mut grouped_rows: ahash::AHashMap<_, Vec<#name>> = ahash::AHashMap::new();
+                for row in rows {
+                    let bucket = (#to_int).div_euclid(units);
+                    grouped_rows.entry((#store_group bucket,)).or_default().push(row);
+                }
+                let fields = Fields::default();
+                let select_sql = format!(#select_sql, fields.select());
+                let transaction = db.transaction().await?;
+                let mut merged_groups = Vec::new();
+                for ((.., bucket), rows) in grouped_rows {
+                    let start = system_time_from_int(bucket * units, #nanos_per_unit)?;
+                    let end = system_time_from_int(bucket * units + (units - 1), #nanos_per_unit)?;
+                    let mut merged = Vec::new();
+                    let mut ctids = Vec::new();
+                    for row in transaction.query(&#prepare_select, &[#group_params &start, &end]).await? {
+                        ctids.push(row.get::<_, String>(row.len() - 1));
+                        merged.extend(fields.load_from_row(row, None)?.decompress()?);
+                    }
+                    if !ctids.is_empty() {
+                        transaction.execute(&transaction.prepare_cached(#delete_sql).await?, &[#group_params &ctids]).await?;
+                    }
+                    merged.extend(rows);
+                    merged_groups.push(merged);
+                }
+                let sql = #store_sql;
+                let types = &[#store_types];
+                let stmt = transaction.copy_in(&transaction.prepare_cached(&sql).await?).await?;
+                let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
+                futures::pin_mut!(writer);
+                let groups = merged_groups.into_iter().map(|rows| {
+                    #deduplicate_rows
+                    rows
+                });
+                #write_groups
+                writer.finish().await?;
+                transaction.commit().await?;
+                Ok(())
+            }
+        }
+    });
+
+    let rewrite = (!unique_by.is_empty()).then(|| {
+        quote! {
+            /// Rewrites the groups matching the filter in a single transaction, merging overlapping groups and
+            /// dropping duplicate rows. When rows share the same `group_by` and `unique_by` values, the one written
+            /// last is kept, as recorded by the table's `write_seq` column. The transaction is rolled back if it fails,
+            /// or if the returned future is dropped before it completes.
+            pub async fn rewrite(db: &mut deadpool_postgres::ClientWrapper, filter: Filter) -> anyhow::Result<()> {
+                let transaction = db.transaction().await?;
+                let mut grouped_rows: ahash::AHashMap<_, Vec<#name>> = ahash::AHashMap::new();
+                for group in Self::delete_in_transaction(&transaction, filter, Fields::default()).await? {
+                    for row in group.decompress()? {
+                        grouped_rows.entry((#store_group)).or_default().push(row);
+                    }
+                }
+                if grouped_rows.is_empty() {
+                    return Ok(());
+                }
+                let sql = #store_sql;
+                let types = &[#store_types];
+                let stmt = transaction.copy_in(&transaction.prepare_cached(&sql).await?).await?;
+                let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
+                futures::pin_mut!(writer);
+                let groups = grouped_rows.into_values().map(|rows| {
+                    #deduplicate_rows
+                    rows
+                });
+                #write_groups
+                writer.finish().await?;
+                transaction.commit().await?;
+                Ok(())
+            }
+        }
+    });
+
+    quote! {
+        #sort_order
+
+        #deduplicate
+
+        #split_rows
+
+        #compress_group
+
+        /// Writes the data to disk.
+        pub async fn store(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = #name>,
+        ) -> anyhow::Result<()> {
+            #deduplicate_rows
+            let mut grouped_rows: ahash::AHashMap<_, Vec<#name>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows.entry((#store_group)).or_default().push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = #store_sql;
+            let types = &[#store_types];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
+            futures::pin_mut!(writer);
+            let groups = grouped_rows.into_values();
+            #write_groups
+            writer.finish().await?;
+            Ok(())
+        }
+
+        /// Writes the data to disk, with the provided grouping closure applied.
+        ///
+        /// This can be used to improve the compression ratio and reduce read IO, for example
+        /// by compacting real-time data into a single row per hour / day / week.
+        pub async fn store_grouped<F, R>(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = #name>,
+            grouping: F,
+        ) -> anyhow::Result<()>
+        where
+            F: Fn(&#name) -> R,
+            R: Eq + std::hash::Hash,
+        {
+            #deduplicate_rows
+            let mut grouped_rows: ahash::AHashMap<_, Vec<#name>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows.entry((#store_group grouping(&row))).or_default().push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = #store_sql;
+            let types = &[#store_types];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
+            futures::pin_mut!(writer);
+            let groups = grouped_rows.into_values();
+            #write_groups
+            writer.finish().await?;
+            Ok(())
+        }
+
+        /// Writes the data to disk as it's received from a stream, for bulk imports that don't fit in memory.
+        ///
+        /// Rows are grouped the same way as [store][Self::store], and each group is written once it has `group_size`
+        /// rows. Any remaining groups are written when the stream ends. Duplicates are only removed within a group.
+        pub async fn store_stream(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl futures::Stream<Item = #name>,
+            group_size: usize,
+        ) -> anyhow::Result<()> {
+            if group_size == 0 {
+                return Err(anyhow::Error::msg("group_size must be greater than zero"));
+            }
+            let sql = #store_sql;
+            let types = &[#store_types];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
+            futures::pin_mut!(writer);
+            futures::pin_mut!(rows);
+            let mut grouped_rows: ahash::AHashMap<_, Vec<#name>> = ahash::AHashMap::new();
+            while let Some(row) = futures::StreamExt::next(&mut rows).await {
+                let group = grouped_rows.entry((#store_group)).or_default();
+                group.push(row);
+                if group.len() >= group_size {
+                    let rows = std::mem::take(group);
+                    #deduplicate_rows
+                    let groups = std::iter::once(rows);
+                    #write_groups
+                }
+            }
+            let groups = grouped_rows.into_values().filter(|rows| !rows.is_empty()).map(|rows| {
+                #deduplicate_rows
+                rows
+            });
+            #write_groups
+            writer.finish().await?;
+            Ok(())
+        }
+
+        #store_merge
+
+        #rewrite
+    }
+}
+
+/// The Postgres type of a `group_by` field for the binary `COPY` used by `store`, inferred for the types that
+/// tokio_postgres supports. Other types, like newtypes, set it with `#[pco(sql_type = "...")]`.
+///
+/// Like [TimestampType], names that are used by more than one crate, like `Date` and `Timestamp`, must be written with
+/// the crate in their path. That way a user type with the same name isn't mistaken for one of these.
+pub fn copy_type(ty: &Type) -> Option<&'static str> {
+    let (krate, segment) = type_path(ty)?;
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => Some(args.args.to_token_stream().to_string()),
+        _ => None,
+    };
+    let copy_type = match (krate.as_deref(), segment.ident.to_string().as_str()) {
+        (None | Some("std" | "core"), "bool") => "BOOL",
+        (None | Some("std" | "core"), "i8") => "CHAR",
+        (None | Some("std" | "core"), "i16") => "INT2",
+        (None | Some("std" | "core"), "i32") => "INT4",
+        (None | Some("std" | "core"), "u32") => "OID",
+        (None | Some("std" | "core"), "i64") => "INT8",
+        (None | Some("std" | "core"), "f32") => "FLOAT4",
+        (None | Some("std" | "core"), "f64") => "FLOAT8",
+        (None | Some("std" | "alloc"), "String") => "TEXT",
+        (None | Some("std" | "alloc"), "Vec") if args.as_deref() == Some("u8") => "BYTEA",
+        (None | Some("std"), "SystemTime") => "TIMESTAMPTZ",
+        (None | Some("time"), "OffsetDateTime") => "TIMESTAMPTZ",
+        (Some("jiff"), "Timestamp") => "TIMESTAMPTZ",
+        (None | Some("chrono"), "DateTime") if args.is_some() => "TIMESTAMPTZ",
+        // `jiff::civil::DateTime` doesn't have a time zone, unlike `chrono::DateTime<Tz>`
+        (Some("jiff"), "DateTime") => "TIMESTAMP",
+        (None | Some("chrono"), "NaiveDateTime") | (None | Some("time"), "PrimitiveDateTime") => "TIMESTAMP",
+        (None | Some("chrono"), "NaiveDate") | (Some("time" | "jiff"), "Date") => "DATE",
+        (None | Some("chrono"), "NaiveTime") | (Some("time" | "jiff"), "Time") => "TIME",
+        (None | Some("std" | "core"), "IpAddr") => "INET",
+        (None | Some("uuid"), "Uuid") => "UUID",
+        (None | Some("rust_decimal"), "Decimal") => "NUMERIC",
+        (None | Some("eui48"), "MacAddress") => "MACADDR",
+        _ => return None,
+    };
+    Some(copy_type)
+}
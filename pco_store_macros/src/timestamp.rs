@@ -0,0 +1,293 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Error, Ident, PathArguments, PathSegment, Result, Type};
+
+/// The unit that timestamps are stored in, as a signed offset from the Unix epoch.
+#[derive(Clone, Copy, Default)]
+pub enum TimestampPrecision {
+    Seconds,
+    Millis,
+    #[default]
+    Micros,
+    Nanos,
+}
+
+impl TimestampPrecision {
+    pub fn parse(ident: &Ident) -> Result<Self> {
+        match ident.to_string().as_str() {
+            "seconds" => Ok(Self::Seconds),
+            "millis" => Ok(Self::Millis),
+            "micros" => Ok(Self::Micros),
+            "nanos" => Ok(Self::Nanos),
+            _ => Err(Error::new(ident.span(), "timestamp_precision must be one of: seconds, millis, micros, nanos")),
+        }
+    }
+
+    pub fn units_per_second(self) -> i64 {
+        match self {
+            Self::Seconds => 1,
+            Self::Millis => 1_000,
+            Self::Micros => 1_000_000,
+            Self::Nanos => 1_000_000_000,
+        }
+    }
+
+    pub fn nanos_per_unit(self) -> i128 {
+        1_000_000_000 / self.units_per_second() as i128
+    }
+
+    /// Converts a timestamp expression into an `i64` offset from the Unix epoch.
+    ///
+    /// Sub-unit precision is truncated towards negative infinity, so times before the epoch round the same way as times after it.
+    /// The expression uses `?`, so it must be placed in a function that returns `anyhow::Result`.
+    pub fn encode(self, value: TokenStream, kind: TimestampType) -> TokenStream {
+        let nanos_per_unit = self.nanos_per_unit();
+        let chrono = |value: TokenStream| match self {
+            Self::Seconds => quote! { #value.timestamp() },
+            Self::Millis => quote! { #value.timestamp_millis() },
+            Self::Micros => quote! { #value.timestamp_micros() },
+            Self::Nanos => quote! {
+                #value.timestamp_nanos_opt().ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?
+            },
+        };
+        let nanos = |nanos: TokenStream| {
+            quote! {
+                i64::try_from(#nanos.div_euclid(#nanos_per_unit)).map_err(|_| anyhow::Error::msg("timestamp out of range"))?
+            }
+        };
+        match kind {
+            TimestampType::SystemTime => quote! { system_time_to_int(#value, #nanos_per_unit)? },
+            TimestampType::Chrono | TimestampType::ChronoFixedOffset => chrono(value),
+            TimestampType::ChronoNaive => chrono(quote! { #value.and_utc() }),
+            TimestampType::Time => nanos(quote! { #value.unix_timestamp_nanos() }),
+            TimestampType::Jiff => nanos(quote! { #value.as_nanosecond() }),
+        }
+    }
+
+    /// Converts an `i64` offset from the Unix epoch back into the timestamp type.
+    ///
+    /// The expression uses `?`, so it must be placed in a function that returns `anyhow::Result`.
+    pub fn decode(self, value: TokenStream, kind: TimestampType) -> TokenStream {
+        let nanos_per_unit = self.nanos_per_unit();
+        let chrono = match self {
+            Self::Seconds => quote! { chrono::DateTime::from_timestamp(#value, 0) },
+            Self::Millis => quote! { chrono::DateTime::from_timestamp_millis(#value) },
+            Self::Micros => quote! { chrono::DateTime::from_timestamp_micros(#value) },
+            Self::Nanos => quote! { Some(chrono::DateTime::from_timestamp_nanos(#value)) },
+        };
+        let time = match kind {
+            TimestampType::SystemTime => return quote! { system_time_from_int(#value, #nanos_per_unit)? },
+            TimestampType::Chrono => chrono,
+            TimestampType::ChronoFixedOffset => quote! { #chrono.map(|t| t.fixed_offset()) },
+            TimestampType::ChronoNaive => quote! { #chrono.map(|t| t.naive_utc()) },
+            TimestampType::Time => quote! { time::OffsetDateTime::from_unix_timestamp_nanos(#value as i128 * #nanos_per_unit).ok() },
+            TimestampType::Jiff => quote! { jiff::Timestamp::from_nanosecond(#value as i128 * #nanos_per_unit).ok() },
+        };
+        quote! { #time.ok_or_else(|| anyhow::Error::msg("timestamp out of range"))? }
+    }
+}
+
+/// The supported timestamp types. Types other than `SystemTime` and `chrono::DateTime<Utc>` require a cargo feature.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum TimestampType {
+    #[default]
+    SystemTime,
+    Chrono,
+    ChronoFixedOffset,
+    ChronoNaive,
+    Time,
+    Jiff,
+}
+
+impl TimestampType {
+    pub fn detect(ty: &Type) -> Result<Self> {
+        let kind = Self::parse(ty, true).ok_or_else(|| Error::new_spanned(ty, "unsupported timestamp type"))?;
+        if let Some(feature) = kind.missing_feature() {
+            let message = format!("the `{feature}` feature of pco_store must be enabled to use this timestamp type");
+            return Err(Error::new_spanned(ty, message));
+        }
+        Ok(kind)
+    }
+
+    /// `ambiguous` also matches names used by other crates without the crate in the path, like `Timestamp`, which is
+    /// only done for the `timestamp` field since it must be a timestamp.
+    fn parse(ty: &Type, ambiguous: bool) -> Option<Self> {
+        let (krate, segment) = type_path(ty)?;
+        let kind = match (krate.as_deref(), segment.ident.to_string().as_str()) {
+            (None | Some("std"), "SystemTime") => Self::SystemTime,
+            // `jiff::civil::DateTime` doesn't have type arguments, unlike `chrono::DateTime<Tz>`
+            (None | Some("chrono"), "DateTime") => match &segment.arguments {
+                PathArguments::None if krate.is_some() || ambiguous => Self::Chrono,
+                PathArguments::AngleBracketed(args) => match args.args.to_token_stream().to_string().as_str() {
+                    "Utc" | "chrono :: Utc" => Self::Chrono,
+                    "FixedOffset" | "chrono :: FixedOffset" => Self::ChronoFixedOffset,
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            (None | Some("chrono"), "NaiveDateTime") => Self::ChronoNaive,
+            (None | Some("time"), "OffsetDateTime") => Self::Time,
+            (Some("jiff"), "Timestamp") => Self::Jiff,
+            (None, "Timestamp") if ambiguous => Self::Jiff,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn missing_feature(self) -> Option<&'static str> {
+        match self {
+            Self::SystemTime | Self::Chrono => None,
+            Self::ChronoFixedOffset | Self::ChronoNaive => (!cfg!(feature = "chrono")).then_some("chrono"),
+            Self::Time => (!cfg!(feature = "time")).then_some("time"),
+            Self::Jiff => (!cfg!(feature = "jiff")).then_some("jiff"),
+        }
+    }
+
+    /// The type returned by subtracting two timestamps
+    pub fn duration_type(self) -> TokenStream {
+        match self {
+            Self::SystemTime => quote! { std::time::Duration },
+            Self::Chrono | Self::ChronoFixedOffset | Self::ChronoNaive => quote! { chrono::Duration },
+            Self::Time => quote! { time::Duration },
+            Self::Jiff => quote! { jiff::SignedDuration },
+        }
+    }
+
+    /// Subtracts the `start` timestamp from `end`
+    pub fn duration_math(self) -> TokenStream {
+        match self {
+            Self::SystemTime => quote! { end.duration_since(start)? },
+            Self::Chrono | Self::ChronoFixedOffset | Self::ChronoNaive | Self::Time => quote! { end - start },
+            Self::Jiff => quote! { end.duration_since(start) },
+        }
+    }
+
+    /// Converts a timestamp into a value that can be used with a `timestamptz` column, if the type can't be used directly.
+    ///
+    /// `SystemTime` is used for other types, so tokio_postgres doesn't need features for them.
+    pub fn sql_value(self, value: TokenStream) -> Option<TokenStream> {
+        match self {
+            Self::SystemTime => None,
+            Self::ChronoNaive => Some(quote! { std::time::SystemTime::from(#value.and_utc()) }),
+            Self::Chrono | Self::ChronoFixedOffset | Self::Time | Self::Jiff => Some(quote! { std::time::SystemTime::from(#value) }),
+        }
+    }
+}
+
+/// The supported duration types. `time` and `jiff` durations require a cargo feature.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DurationType {
+    Std,
+    Chrono,
+    Time,
+    Jiff,
+}
+
+impl DurationType {
+    /// `Duration` is defined by several crates, so an unqualified `Duration` is an error instead of falling back to
+    /// MessagePack without the user noticing.
+    pub fn check_ambiguous(ty: &Type) -> Result<()> {
+        match type_path(ty) {
+            Some((None, segment)) if segment.ident == "Duration" && segment.arguments.is_none() => {
+                let message = "`Duration` is defined by several crates, write it with its path, like `std::time::Duration`, `chrono::Duration` or `time::Duration`";
+                Err(Error::new_spanned(ty, message))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `Duration` is defined by several crates, so it's only matched with the crate in the path, like `std::time::Duration`
+    fn parse(ty: &Type) -> Option<Self> {
+        let (krate, segment) = type_path(ty)?;
+        let kind = match (krate.as_deref(), segment.ident.to_string().as_str()) {
+            (Some("std" | "core"), "Duration") => Self::Std,
+            (Some("chrono"), "Duration") | (None | Some("chrono"), "TimeDelta") => Self::Chrono,
+            (Some("time"), "Duration") => Self::Time,
+            (None | Some("jiff"), "SignedDuration") => Self::Jiff,
+            _ => return None,
+        };
+        match kind {
+            Self::Time if !cfg!(feature = "time") => None,
+            Self::Jiff if !cfg!(feature = "jiff") => None,
+            _ => Some(kind),
+        }
+    }
+}
+
+/// Returns the crate at the start of a type's path, if it has more than one segment, and its last segment
+pub fn type_path(ty: &Type) -> Option<(Option<String>, &PathSegment)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segments = &path.path.segments;
+    let krate = (segments.len() > 1).then(|| segments[0].ident.to_string());
+    Some((krate, segments.last()?))
+}
+
+/// Time fields are compressed with pco as integers in the `timestamp_precision` unit, and duration fields as
+/// microseconds, so a coarse `timestamp_precision` doesn't drop the sub-second part of durations.
+///
+/// Types that need a cargo feature that isn't enabled, or whose name is used by more than one crate and isn't written
+/// with the crate in its path, aren't detected and fall back to MessagePack.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TimeField {
+    Timestamp(TimestampType),
+    Duration(DurationType),
+}
+
+impl TimeField {
+    pub fn detect(ty: &Type) -> Option<Self> {
+        if let Some(kind) = TimestampType::parse(ty, false).filter(|k| k.missing_feature().is_none()) {
+            return Some(Self::Timestamp(kind));
+        }
+        DurationType::parse(ty).map(Self::Duration)
+    }
+
+    /// Converts a time or duration expression into an `i64`. See [TimestampPrecision::encode].
+    pub fn encode(self, precision: TimestampPrecision, value: TokenStream) -> TokenStream {
+        let kind = match self {
+            Self::Timestamp(kind) => return precision.encode(value, kind),
+            Self::Duration(kind) => kind,
+        };
+        let nanos = match kind {
+            DurationType::Std => quote! { (#value.as_nanos() as i128) },
+            DurationType::Chrono => quote! { (#value.num_seconds() as i128 * 1_000_000_000 + #value.subsec_nanos() as i128) },
+            DurationType::Time => quote! { #value.whole_nanoseconds() },
+            DurationType::Jiff => quote! { #value.as_nanos() },
+        };
+        let nanos_per_unit = TimestampPrecision::Micros.nanos_per_unit();
+        quote! {
+            i64::try_from(#nanos.div_euclid(#nanos_per_unit)).map_err(|_| anyhow::Error::msg("duration out of range"))?
+        }
+    }
+
+    /// Converts an `i64` back into the time or duration type. See [TimestampPrecision::decode].
+    pub fn decode(self, precision: TimestampPrecision, value: TokenStream) -> TokenStream {
+        let kind = match self {
+            Self::Timestamp(kind) => return precision.decode(value, kind),
+            Self::Duration(kind) => kind,
+        };
+        let duration = match kind {
+            DurationType::Std => quote! { u64::try_from(secs).ok().map(|secs| std::time::Duration::new(secs, nanos)) },
+            DurationType::Chrono => quote! { chrono::Duration::new(secs, nanos) },
+            DurationType::Time => quote! { Some(time::Duration::new(secs, nanos as i32)) },
+            DurationType::Jiff => quote! { Some(jiff::SignedDuration::new(secs, nanos as i32)) },
+        };
+        let nanos_per_unit = TimestampPrecision::Micros.nanos_per_unit();
+        quote! {
+            {
+                let (secs, nanos) = duration_parts(#value, #nanos_per_unit);
+                #duration
+            }
+            .ok_or_else(|| anyhow::Error::msg("duration out of range"))?
+        }
+    }
+
+    /// The function that deserializes a filter range of this type
+    pub fn deserialize_with(self, ty: &Type, filter_name: &Ident) -> String {
+        match self {
+            Self::Timestamp(TimestampType::Time) => format!("{filter_name}::deserialize_time_range_rfc3339"),
+            _ => format!("::pco_store::__private::deserialize_time_range::<_, _, {}>", quote! { #ty }),
+        }
+    }
+}
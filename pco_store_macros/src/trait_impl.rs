@@ -0,0 +1,71 @@
+use super::Arguments;
+use quote::quote;
+use syn::{Ident, ItemStruct};
+
+/// Implements the `PcoStore` trait by calling the inherent methods
+pub fn generate(model: &ItemStruct, args: &Arguments, packed_name: &Ident, table_name: &str) -> proc_macro2::TokenStream {
+    let name = model.ident.clone();
+    let Arguments { timestamp, group_by, .. } = args;
+    let mut columns = Vec::new();
+    for field in model.fields.iter() {
+        let ident = field.ident.clone().unwrap();
+        if timestamp.as_ref() == Some(&ident) && !group_by.contains(&ident) {
+            columns.push("start_at".to_string());
+            columns.push("end_at".to_string());
+        }
+        columns.push(ident.to_string());
+    }
+    let group_by = group_by.iter().map(|ident| ident.to_string());
+    let timestamp = match timestamp {
+        Some(timestamp) => {
+            let timestamp = timestamp.to_string();
+            quote! { Some(#timestamp) }
+        }
+        None => quote! { None },
+    };
+
+    quote! {
+        impl ::pco_store::PcoStore for #packed_name {
+            type Row = #name;
+            type Filter = Filter;
+            type Fields = Fields;
+
+            const TABLE_NAME: &'static str = #table_name;
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+            const GROUP_BY: &'static [&'static str] = &[#(#group_by),*];
+            const TIMESTAMP: Option<&'static str> = #timestamp;
+
+            fn store<D>(
+                db: &D,
+                rows: impl IntoIterator<Item = #name> + Send,
+            ) -> impl std::future::Future<Output = anyhow::Result<()>> + Send
+            where
+                D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+            {
+                Self::store(db, rows)
+            }
+
+            fn load<D>(db: &D, filter: Filter, fields: Fields) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+            where
+                D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+            {
+                Self::load(db, filter, fields)
+            }
+
+            fn delete<D>(db: &D, filter: Filter, fields: Fields) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+            where
+                D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+            {
+                Self::delete(db, filter, fields)
+            }
+
+            fn decompress(self) -> anyhow::Result<Vec<#name>> {
+                Self::decompress(self)
+            }
+
+            fn decompress_all(groups: Vec<Self>) -> impl std::future::Future<Output = anyhow::Result<Vec<#name>>> + Send {
+                Self::decompress_all(groups)
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Compression helpers used by the generated code
+
+pub fn serde_compress<T>(items: Vec<T>) -> anyhow::Result<Vec<u8>>
+where
+    T: serde::Serialize,
+{
+    let mut output = Vec::new();
+    let mut encoder = zstd::stream::write::Encoder::new(&mut output, 3)?;
+    for item in items {
+        rmp_serde::encode::write(&mut encoder, &item)?;
+    }
+    encoder.finish()?;
+    Ok(output)
+}
+
+pub fn serde_decompress<T>(input: &[u8]) -> impl Iterator<Item = anyhow::Result<T>> + '_
+where
+    T: for<'de> serde::Deserialize<'de> + 'static,
+{
+    let decoder = match zstd::stream::read::Decoder::new(input) {
+        Ok(d) => d,
+        Err(e) => return Box::new(std::iter::once(Err(e.into()))) as Box<dyn Iterator<Item = _>>,
+    };
+    let buffered = std::io::BufReader::with_capacity(128 * 1024, decoder);
+    let mut de = rmp_serde::decode::Deserializer::new(buffered);
+    Box::new(std::iter::from_fn(move || match serde::Deserialize::deserialize(&mut de) {
+        Ok(item) => Some(Ok(item)),
+        Err(rmp_serde::decode::Error::InvalidMarkerRead(ref e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+        Err(e) => Some(Err(e.into())),
+    }))
+}
+
+/// Timestamps used to be stored as unsigned microseconds, so those are converted to the current precision
+pub fn pco_decompress_timestamps(bytes: &[u8], units_per_second: i64) -> anyhow::Result<Vec<i64>> {
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+    if pco_number_type(bytes)? == Some(pco::data_types::NumberType::U64) {
+        let micros = pco::standalone::simple_decompress::<u64>(bytes)?;
+        return Ok(micros.into_iter().map(|m| (m as i128 * units_per_second as i128 / 1_000_000) as i64).collect());
+    }
+    Ok(pco::standalone::simple_decompress::<i64>(bytes)?)
+}
+
+pub fn pco_number_type(bytes: &[u8]) -> anyhow::Result<Option<pco::data_types::NumberType>> {
+    let (decompressor, rest) = pco::standalone::FileDecompressor::new(bytes)?;
+    Ok(decompressor.peek_number_type_or_termination(rest)?)
+}
+
+/// Converts a rounded float to an `i64`, if it's finite and in range
+pub fn float_to_int(value: f64) -> Option<i64> {
+    (value.is_finite() && value >= i64::MIN as f64 && value < i64::MAX as f64).then_some(value as i64)
+}
+
+/// `float_round = auto` stores floats with the fewest decimal places that exactly represent every value in a group.
+/// `round_mult` and `unround_mult` are used by `float_round = N`, where `mult` is 10^N.
+pub trait AutoRound: pco::data_types::Number {
+    fn round_to(self, scale: i32) -> Option<i64>;
+    fn unround(value: i64, scale: i32) -> Self;
+    fn round_mult(self, mult: f32) -> Option<i64>;
+    fn unround_mult(value: i64, mult: f32) -> Self;
+    fn same(self, other: Self) -> bool;
+}
+
+impl AutoRound for f32 {
+    fn round_to(self, scale: i32) -> Option<i64> {
+        float_to_int((self * 10f32.powi(scale)).round() as f64)
+    }
+    fn unround(value: i64, scale: i32) -> Self {
+        value as f32 / 10f32.powi(scale)
+    }
+    fn round_mult(self, mult: f32) -> Option<i64> {
+        float_to_int((self * mult).round() as f64)
+    }
+    fn unround_mult(value: i64, mult: f32) -> Self {
+        value as f32 / mult
+    }
+    fn same(self, other: Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl AutoRound for f64 {
+    fn round_to(self, scale: i32) -> Option<i64> {
+        float_to_int((self * 10f64.powi(scale)).round())
+    }
+    fn unround(value: i64, scale: i32) -> Self {
+        value as f64 / 10f64.powi(scale)
+    }
+    fn round_mult(self, mult: f32) -> Option<i64> {
+        float_to_int((self * mult as f64).round())
+    }
+    fn unround_mult(value: i64, mult: f32) -> Self {
+        value as f64 / mult as f64
+    }
+    fn same(self, other: Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+/// The payload starts with the chosen scale, or `None` if the original floats were stored
+pub fn pco_compress_auto_round<T: AutoRound>(values: Vec<T>) -> anyhow::Result<Vec<u8>> {
+    let rounded = (0..=15u8).find_map(|scale| {
+        let exact = |v: &T| v.round_to(scale as i32).filter(|r| T::unround(*r, scale as i32).same(*v));
+        values.iter().map(exact).collect::<Option<Vec<i64>>>().map(|rounded| (scale, rounded))
+    });
+    let (scale, bytes) = match rounded {
+        Some((scale, rounded)) => (Some(scale), pco::standalone::simple_compress(&rounded, &pco::ChunkConfig::default())?),
+        None => (None, pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?),
+    };
+    Ok(rmp_serde::to_vec(&(scale, serde_bytes::Bytes::new(&bytes)))?)
+}
+
+/// Groups written before `float_round = auto` was set stored the original floats, which start with pco's header
+pub fn pco_decompress_auto_round<T: AutoRound>(bytes: &[u8]) -> anyhow::Result<Vec<T>> {
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+    if bytes.starts_with(b"pco!") {
+        return Ok(pco::standalone::simple_decompress::<T>(bytes)?);
+    }
+    let (scale, bytes): (Option<u8>, Vec<u8>) = rmp_serde::from_slice(bytes)?;
+    match scale {
+        Some(scale) => {
+            let values = pco::standalone::simple_decompress::<i64>(&bytes)?;
+            Ok(values.into_iter().map(|v| T::unround(v, scale as i32)).collect())
+        }
+        None => Ok(pco::standalone::simple_decompress::<T>(&bytes)?),
+    }
+}
+
+/// `float_round = N` stores values multiplied by 10^N and rounded to an `i64`. Groups with values that can't be
+/// rounded, like NaN, infinity or values that overflow an `i64`, store the original floats instead.
+pub fn pco_compress_round<T: AutoRound>(values: Vec<T>, mult: f32) -> anyhow::Result<Vec<u8>> {
+    let rounded: Option<Vec<i64>> = values.iter().map(|v| v.round_mult(mult)).collect();
+    match rounded {
+        Some(rounded) => Ok(pco::standalone::simple_compress(&rounded, &pco::ChunkConfig::default())?),
+        None => Ok(pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?),
+    }
+}
+
+pub fn pco_decompress_round<T: AutoRound>(bytes: &[u8], mult: f32) -> anyhow::Result<Vec<T>> {
+    if bytes.is_empty() {
+        Ok(Vec::new())
+    } else if pco_number_type(bytes)? == Some(pco::data_types::NumberType::I64) {
+        let values = pco::standalone::simple_decompress::<i64>(bytes)?;
+        Ok(values.into_iter().map(|v| T::unround_mult(v, mult)).collect())
+    } else {
+        Ok(pco::standalone::simple_decompress(bytes)?)
+    }
+}
+
+pub fn system_time_to_int(time: std::time::SystemTime, nanos_per_unit: i128) -> anyhow::Result<i64> {
+    let nanos = match time.duration_since(std::time::SystemTime::UNIX_EPOCH) {
+        Ok(duration) => duration.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    };
+    i64::try_from(nanos.div_euclid(nanos_per_unit)).map_err(|_| anyhow::Error::msg("timestamp out of range"))
+}
+
+pub fn system_time_from_int(value: i64, nanos_per_unit: i128) -> anyhow::Result<std::time::SystemTime> {
+    let nanos = value as i128 * nanos_per_unit;
+    let abs = nanos.unsigned_abs();
+    let duration = std::time::Duration::new((abs / 1_000_000_000) as u64, (abs % 1_000_000_000) as u32);
+    let time =
+        if nanos >= 0 { std::time::SystemTime::UNIX_EPOCH.checked_add(duration) } else { std::time::SystemTime::UNIX_EPOCH.checked_sub(duration) };
+    time.ok_or_else(|| anyhow::Error::msg("timestamp out of range"))
+}
+
+/// Splits an `i64` duration into whole seconds and nanoseconds, rounding towards negative infinity
+pub fn duration_parts(value: i64, nanos_per_unit: i128) -> (i64, u32) {
+    let nanos = value as i128 * nanos_per_unit;
+    (nanos.div_euclid(1_000_000_000) as i64, nanos.rem_euclid(1_000_000_000) as u32)
+}
+
+/// Detects fields that were serialized with MessagePack and compressed with zstd before being stored with pco
+pub fn is_zstd(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD])
+}
+
+/// `Vec` fields are stored as the length of each `Vec`, followed by all of their values
+pub(crate) fn nested_split<T>(nested_values: Vec<Vec<T>>) -> (Vec<u64>, Vec<T>) {
+    let mut lengths = Vec::with_capacity(nested_values.len());
+    let mut values = Vec::new();
+    for vals in nested_values {
+        lengths.push(vals.len() as u64);
+        values.extend(vals);
+    }
+    (lengths, values)
+}
+
+pub(crate) fn nested_join<T>(lengths: Vec<u64>, values: Vec<T>) -> Vec<Vec<T>> {
+    let mut values = values.into_iter();
+    let mut nested_values = Vec::with_capacity(lengths.len());
+    for length in lengths {
+        nested_values.push(values.by_ref().take(length as usize).collect::<Vec<T>>());
+    }
+    nested_values
+}
+
+pub(crate) fn nested_encode(lengths: &[u64], value_bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let length_bytes = pco::standalone::simple_compress(lengths, &pco::ChunkConfig::default())?;
+    let (length_bytes, value_bytes) = (serde_bytes::Bytes::new(&length_bytes), serde_bytes::Bytes::new(value_bytes));
+    Ok(rmp_serde::to_vec(&(length_bytes, value_bytes))?)
+}
+
+/// Returns the lengths and the bytes of the values, or `None` for fields that weren't loaded
+pub(crate) fn nested_decode(bytes: &[u8]) -> anyhow::Result<Option<(Vec<u64>, Vec<u8>)>> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    let (length_bytes, value_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(bytes)?;
+    Ok(Some((pco::standalone::simple_decompress::<u64>(&length_bytes)?, value_bytes)))
+}
+
+pub fn pco_compress_nested<T>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>>
+where
+    T: pco::data_types::Number,
+{
+    let (lengths, values) = nested_split(nested_values);
+    nested_encode(&lengths, &pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?)
+}
+
+pub fn pco_decompress_nested<T>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>>
+where
+    T: pco::data_types::Number,
+{
+    let Some((lengths, value_bytes)) = nested_decode(&bytes)? else {
+        return Ok(Vec::new());
+    };
+    Ok(nested_join(lengths, pco::standalone::simple_decompress::<T>(&value_bytes)?))
+}
+
+/// `float_round = N` for `Vec` fields, rounding the values the same way as for single values
+pub fn pco_compress_nested_round<T: AutoRound>(nested_values: Vec<Vec<T>>, mult: f32) -> anyhow::Result<Vec<u8>> {
+    let (lengths, values) = nested_split(nested_values);
+    nested_encode(&lengths, &pco_compress_round(values, mult)?)
+}
+
+/// Groups written before `float_round` applied to `Vec` fields stored the original floats, which are still read
+pub fn pco_decompress_nested_round<T: AutoRound>(bytes: Vec<u8>, mult: f32) -> anyhow::Result<Vec<Vec<T>>> {
+    let Some((lengths, value_bytes)) = nested_decode(&bytes)? else {
+        return Ok(Vec::new());
+    };
+    Ok(nested_join(lengths, pco_decompress_round(&value_bytes, mult)?))
+}
+
+/// `float_round = auto` for `Vec` fields, choosing one scale for all of the values in a group
+pub fn pco_compress_nested_auto_round<T: AutoRound>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>> {
+    let (lengths, values) = nested_split(nested_values);
+    nested_encode(&lengths, &pco_compress_auto_round(values)?)
+}
+
+/// Groups written before `float_round` applied to `Vec` fields stored the original floats, which are still read
+pub fn pco_decompress_nested_auto_round<T: AutoRound>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>> {
+    let Some((lengths, value_bytes)) = nested_decode(&bytes)? else {
+        return Ok(Vec::new());
+    };
+    Ok(nested_join(lengths, pco_decompress_auto_round(&value_bytes)?))
+}
+
+/// 128-bit integers are split into their high and low 64 bits, which are compressed separately
+pub trait WideNumber: Copy {
+    type High: pco::data_types::Number;
+    fn split(self) -> (Self::High, u64);
+    fn join(high: Self::High, low: u64) -> Self;
+}
+
+impl WideNumber for i128 {
+    type High = i64;
+    fn split(self) -> (i64, u64) {
+        ((self >> 64) as i64, self as u64)
+    }
+    fn join(high: i64, low: u64) -> Self {
+        ((high as i128) << 64) | low as i128
+    }
+}
+
+impl WideNumber for u128 {
+    type High = u64;
+    fn split(self) -> (u64, u64) {
+        ((self >> 64) as u64, self as u64)
+    }
+    fn join(high: u64, low: u64) -> Self {
+        ((high as u128) << 64) | low as u128
+    }
+}
+
+pub fn pco_compress_wide<T: WideNumber>(values: Vec<T>) -> anyhow::Result<Vec<u8>> {
+    let (high, low): (Vec<T::High>, Vec<u64>) = values.into_iter().map(T::split).unzip();
+    let high_bytes = pco::standalone::simple_compress(&high, &pco::ChunkConfig::default())?;
+    let low_bytes = pco::standalone::simple_compress(&low, &pco::ChunkConfig::default())?;
+    let (high_bytes, low_bytes) = (serde_bytes::Bytes::new(&high_bytes), serde_bytes::Bytes::new(&low_bytes));
+    Ok(rmp_serde::to_vec(&(high_bytes, low_bytes))?)
+}
+
+pub fn pco_decompress_wide<T: WideNumber>(bytes: &[u8]) -> anyhow::Result<Vec<T>> {
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (high_bytes, low_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(bytes)?;
+    let high = pco::standalone::simple_decompress::<T::High>(&high_bytes)?;
+    let low = pco::standalone::simple_decompress::<u64>(&low_bytes)?;
+    Ok(high.into_iter().zip(low).map(|(high, low)| T::join(high, low)).collect())
+}
+
+pub fn pco_compress_nested_wide<T: WideNumber>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>> {
+    let (lengths, values) = nested_split(nested_values);
+    nested_encode(&lengths, &pco_compress_wide(values)?)
+}
+
+pub fn pco_decompress_nested_wide<T: WideNumber>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>> {
+    let Some((lengths, value_bytes)) = nested_decode(&bytes)? else {
+        return Ok(Vec::new());
+    };
+    Ok(nested_join(lengths, pco_decompress_wide::<T>(&value_bytes)?))
+}
@@ -0,0 +1,580 @@
+//! Compression of types that implement [PcoNumeric], like newtypes and type aliases of numbers
+
+use super::compress::*;
+use half::f16;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+/// Types that are compressed with pco, by converting them to and from one of pco's number types.
+///
+/// Fields with a built-in number type are detected by the macro, but type aliases and newtypes are only known
+/// to be numbers through this trait. Implement it for a newtype to compress it with pco instead of MessagePack:
+///
+/// ```
+/// pub struct Calls(i64);
+///
+/// impl pco_store::PcoNumeric for Calls {
+///     type Number = i64;
+///
+///     fn to_number(&self) -> i64 {
+///         self.0
+///     }
+///
+///     fn from_number(number: i64) -> Self {
+///         Self(number)
+///     }
+/// }
+/// ```
+///
+/// Data that was written with MessagePack before implementing this trait is still read, as long as the
+/// MessagePack values can be deserialized as `Number`.
+pub trait PcoNumeric: Sized {
+    type Number: PcoNumber;
+
+    fn to_number(&self) -> Self::Number;
+    fn from_number(number: Self::Number) -> Self;
+}
+
+macro_rules! impl_pco_numeric {
+    ($($ty:ty),*) => {
+        $(
+            impl PcoNumeric for $ty {
+                type Number = $ty;
+
+                fn to_number(&self) -> $ty {
+                    *self
+                }
+
+                fn from_number(number: $ty) -> Self {
+                    number
+                }
+            }
+        )*
+    };
+}
+impl_pco_numeric!(u16, u32, u64, i16, i32, i64, f16, f32, f64);
+
+/// How `float_round` stores the floats of a field, passed to [PcoNumber] by the generated code
+#[derive(Clone, Copy)]
+pub enum FloatRound {
+    Fixed(f32),
+    Auto,
+}
+
+/// The number types that pco compresses, which [PcoNumeric] values are converted to.
+///
+/// Options like `float_round` and `sort_by` go through this trait, so they apply the same way to type aliases and
+/// newtypes as to the numbers themselves. `float_round` only changes `f32` and `f64`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't a number type that pco can compress",
+    note = "pco compresses 8 to 64-bit integers, `f32`, `f64` and `half::f16`. `i128` and `u128` fields are split into two 64-bit columns by the macro, but can't be used through `PcoNumeric`"
+)]
+pub trait PcoNumber: pco::data_types::Number + serde::de::DeserializeOwned + 'static {
+    fn compress_round(values: Vec<Self>, _round: FloatRound) -> anyhow::Result<Vec<u8>> {
+        Ok(pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?)
+    }
+
+    fn decompress_round(bytes: &[u8], _round: FloatRound) -> anyhow::Result<Vec<Self>> {
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(pco::standalone::simple_decompress(bytes)?)
+    }
+
+    /// A total order, which for floats puts NaN values after all others
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_pco_number {
+    ($($ty:ty),*) => {
+        $(
+            impl PcoNumber for $ty {
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+impl_pco_number!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl PcoNumber for f16 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f16::total_cmp(self, other)
+    }
+}
+
+macro_rules! impl_pco_float {
+    ($($ty:ty),*) => {
+        $(
+            impl PcoNumber for $ty {
+                fn compress_round(values: Vec<Self>, round: FloatRound) -> anyhow::Result<Vec<u8>> {
+                    match round {
+                        FloatRound::Fixed(mult) => pco_compress_round(values, mult),
+                        FloatRound::Auto => pco_compress_auto_round(values),
+                    }
+                }
+
+                fn decompress_round(bytes: &[u8], round: FloatRound) -> anyhow::Result<Vec<Self>> {
+                    match round {
+                        FloatRound::Fixed(mult) => pco_decompress_round(bytes, mult),
+                        FloatRound::Auto => pco_decompress_auto_round(bytes),
+                    }
+                }
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    <$ty>::total_cmp(self, other)
+                }
+            }
+        )*
+    };
+}
+impl_pco_float!(f32, f64);
+
+/// pco doesn't compress 8-bit types by default, so they're stored as 16-bit integers
+impl PcoNumeric for u8 {
+    type Number = u16;
+
+    fn to_number(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_number(number: u16) -> Self {
+        number as u8
+    }
+}
+
+impl PcoNumeric for i8 {
+    type Number = i16;
+
+    fn to_number(&self) -> i16 {
+        *self as i16
+    }
+
+    fn from_number(number: i16) -> Self {
+        number as i8
+    }
+}
+
+impl PcoNumeric for bool {
+    type Number = u16;
+
+    fn to_number(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_number(number: u16) -> Self {
+        number == 1
+    }
+}
+
+/// Maps with [PcoNumeric] values, like `BTreeMap<String, i64>`, which store their keys in a dictionary and compress
+/// their values with pco
+pub trait PcoMap: Sized {
+    type Key: serde::Serialize + serde::de::DeserializeOwned + Clone;
+    type Value: PcoNumeric;
+    /// The map with the values deserialized as numbers, for data that was written with MessagePack
+    type Legacy: serde::de::DeserializeOwned + IntoIterator<Item = (Self::Key, <Self::Value as PcoNumeric>::Number)>;
+
+    fn entries(&self) -> impl Iterator<Item = (&Self::Key, &Self::Value)>;
+    fn from_entries(entries: impl Iterator<Item = (Self::Key, Self::Value)>) -> Self;
+}
+
+impl<K, V> PcoMap for BTreeMap<K, V>
+where
+    K: serde::Serialize + serde::de::DeserializeOwned + Clone + Ord,
+    V: PcoNumeric,
+{
+    type Key = K;
+    type Value = V;
+    type Legacy = BTreeMap<K, V::Number>;
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn from_entries(entries: impl Iterator<Item = (K, V)>) -> Self {
+        entries.collect()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> PcoMap for indexmap::IndexMap<K, V, S>
+where
+    K: serde::Serialize + serde::de::DeserializeOwned + Clone + std::hash::Hash + Eq,
+    V: PcoNumeric,
+    S: std::hash::BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+    type Legacy = indexmap::IndexMap<K, V::Number, S>;
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn from_entries(entries: impl Iterator<Item = (K, V)>) -> Self {
+        entries.collect()
+    }
+}
+
+/// The type of a field that the macro couldn't identify as a number. Calling `kind()` on `&&&Column<T>` uses
+/// autoref specialization to pick the first of these that applies: [PcoNumeric] values or arrays of them,
+/// `Vec`s or `Vec<Vec<_>>`s of them, maps with them as values, or MessagePack.
+pub struct Column<T>(pub PhantomData<T>);
+
+pub struct NumericKind;
+pub struct ArrayKind;
+pub struct NestedKind;
+pub struct DoublyNestedKind;
+pub struct MapKind;
+pub struct SerdeKind;
+
+/// The decompressed values of a field. pco columns are decompressed all at once, which gives the number of rows
+/// in the group, while MessagePack values are deserialized one at a time as the rows are built.
+pub enum Values<'a, T> {
+    Decompressed(std::vec::IntoIter<T>),
+    Streamed(std::iter::Peekable<Box<dyn Iterator<Item = anyhow::Result<T>> + 'a>>),
+}
+
+impl<T> Values<'_, T> {
+    /// The number of rows, if known without deserializing them
+    pub fn known_len(&self) -> Option<usize> {
+        match self {
+            Self::Decompressed(values) => Some(values.len()),
+            Self::Streamed(_) => None,
+        }
+    }
+
+    pub fn has_next(&mut self) -> bool {
+        match self {
+            Self::Decompressed(values) => values.len() > 0,
+            Self::Streamed(values) => values.peek().is_some(),
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Values<'_, T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::Decompressed(values.into_iter())
+    }
+}
+
+impl<T> Iterator for Values<'_, T> {
+    type Item = anyhow::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Decompressed(values) => values.next().map(Ok),
+            Self::Streamed(values) => values.next(),
+        }
+    }
+}
+
+pub trait ViaNumeric {
+    fn kind(&self) -> NumericKind {
+        NumericKind
+    }
+}
+impl<T: PcoNumeric> ViaNumeric for &&Column<T> {}
+
+pub trait ViaArray {
+    fn kind(&self) -> ArrayKind {
+        ArrayKind
+    }
+}
+impl<T: PcoNumeric, const N: usize> ViaArray for &&Column<[T; N]> {}
+
+pub trait ViaNested {
+    fn kind(&self) -> NestedKind {
+        NestedKind
+    }
+}
+impl<T: PcoNumeric> ViaNested for &Column<Vec<T>> {}
+
+pub trait ViaDoublyNested {
+    fn kind(&self) -> DoublyNestedKind {
+        DoublyNestedKind
+    }
+}
+impl<T: PcoNumeric> ViaDoublyNested for &Column<Vec<Vec<T>>> {}
+
+pub trait ViaMap {
+    fn kind(&self) -> MapKind {
+        MapKind
+    }
+}
+impl<M: PcoMap> ViaMap for &Column<M> {}
+
+pub trait ViaSerde {
+    fn kind(&self) -> SerdeKind {
+        SerdeKind
+    }
+}
+impl<T> ViaSerde for Column<T> {}
+
+impl NumericKind {
+    pub fn compress<'a, T: PcoNumeric + 'a>(self, values: impl Iterator<Item = &'a T>) -> anyhow::Result<Vec<u8>> {
+        let values: Vec<T::Number> = values.map(T::to_number).collect();
+        Ok(pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?)
+    }
+
+    pub fn decompress<T: PcoNumeric>(self, bytes: &[u8]) -> anyhow::Result<Vec<T>> {
+        if is_zstd(bytes) {
+            return serde_decompress::<T::Number>(bytes).map(|v| v.map(T::from_number)).collect();
+        }
+        let values = if bytes.is_empty() { Vec::new() } else { pco::standalone::simple_decompress::<T::Number>(bytes)? };
+        Ok(values.into_iter().map(T::from_number).collect())
+    }
+
+    pub fn compress_round<'a, T: PcoNumeric + 'a>(self, values: impl Iterator<Item = &'a T>, round: FloatRound) -> anyhow::Result<Vec<u8>> {
+        T::Number::compress_round(values.map(T::to_number).collect(), round)
+    }
+
+    pub fn decompress_round<T: PcoNumeric>(self, bytes: &[u8], round: FloatRound) -> anyhow::Result<Vec<T>> {
+        if is_zstd(bytes) {
+            return self.decompress(bytes);
+        }
+        Ok(T::Number::decompress_round(bytes, round)?.into_iter().map(T::from_number).collect())
+    }
+
+    pub fn compare<T: PcoNumeric>(self, a: &T, b: &T) -> Ordering {
+        a.to_number().total_cmp(&b.to_number())
+    }
+}
+
+/// Arrays are transposed, so the values at each position are next to each other and compress like their own column
+impl ArrayKind {
+    pub fn compress<'a, T: PcoNumeric + 'a, const N: usize>(self, values: impl Iterator<Item = &'a [T; N]>) -> anyhow::Result<Vec<u8>> {
+        let rows: Vec<&[T; N]> = values.collect();
+        let values: Vec<T::Number> = (0..N).flat_map(|i| rows.iter().map(move |row| row[i].to_number())).collect();
+        Ok(pco::standalone::simple_compress(&values, &pco::ChunkConfig::default())?)
+    }
+
+    pub fn decompress<T: PcoNumeric + 'static, const N: usize>(self, bytes: &[u8]) -> anyhow::Result<Vec<[T; N]>> {
+        // MessagePack encodes arrays the same way as `Vec`s
+        if is_zstd(bytes) {
+            return serde_decompress::<Vec<T::Number>>(bytes)
+                .map(|v| {
+                    let values: Vec<T> = v?.into_iter().map(T::from_number).collect();
+                    values.try_into().map_err(|_| anyhow::Error::msg("array has the wrong length"))
+                })
+                .collect();
+        }
+        let values = if bytes.is_empty() || N == 0 { Vec::new() } else { pco::standalone::simple_decompress::<T::Number>(bytes)? };
+        if values.len() % N.max(1) != 0 {
+            return Err(anyhow::Error::msg("array values don't match the array length"));
+        }
+        let len = values.len() / N.max(1);
+        let mut columns: Vec<_> = values.chunks(len.max(1)).map(|c| c.iter()).collect();
+        Ok((0..len).map(|_| std::array::from_fn(|i| T::from_number(*columns[i].next().unwrap()))).collect())
+    }
+
+    /// `float_round` only applies to single values and `Vec`s, so arrays are stored as they are
+    pub fn compress_round<'a, T: PcoNumeric + 'a, const N: usize>(
+        self, values: impl Iterator<Item = &'a [T; N]>, _round: FloatRound,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.compress(values)
+    }
+
+    pub fn decompress_round<T: PcoNumeric + 'static, const N: usize>(self, bytes: &[u8], _round: FloatRound) -> anyhow::Result<Vec<[T; N]>> {
+        self.decompress(bytes)
+    }
+
+    pub fn compare<V: PartialOrd>(self, a: &V, b: &V) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl NestedKind {
+    pub fn compress<'a, T: PcoNumeric + 'a>(self, values: impl Iterator<Item = &'a Vec<T>>) -> anyhow::Result<Vec<u8>> {
+        let values: Vec<Vec<T::Number>> = values.map(|v| v.iter().map(T::to_number).collect()).collect();
+        pco_compress_nested(values)
+    }
+
+    pub fn decompress<T: PcoNumeric>(self, bytes: &[u8]) -> anyhow::Result<Vec<Vec<T>>> {
+        if is_zstd(bytes) {
+            return serde_decompress::<Vec<T::Number>>(bytes).map(|v| v.map(|v| v.into_iter().map(T::from_number).collect())).collect();
+        }
+        let values = if bytes.is_empty() { Vec::new() } else { pco_decompress_nested::<T::Number>(bytes.to_vec())? };
+        Ok(values.into_iter().map(|v| v.into_iter().map(T::from_number).collect()).collect())
+    }
+
+    pub fn compress_round<'a, T: PcoNumeric + 'a>(self, values: impl Iterator<Item = &'a Vec<T>>, round: FloatRound) -> anyhow::Result<Vec<u8>> {
+        let (lengths, values) = nested_split(values.map(|v| v.iter().map(T::to_number).collect()).collect());
+        nested_encode(&lengths, &T::Number::compress_round(values, round)?)
+    }
+
+    pub fn decompress_round<T: PcoNumeric>(self, bytes: &[u8], round: FloatRound) -> anyhow::Result<Vec<Vec<T>>> {
+        if is_zstd(bytes) {
+            return self.decompress(bytes);
+        }
+        let Some((lengths, value_bytes)) = nested_decode(bytes)? else {
+            return Ok(Vec::new());
+        };
+        let values = T::Number::decompress_round(&value_bytes, round)?.into_iter().map(T::from_number).collect();
+        Ok(nested_join(lengths, values))
+    }
+
+    pub fn compare<V: PartialOrd>(self, a: &V, b: &V) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The outer lengths are stored in front of the `pco_compress_nested` output for the inner `Vec`s
+impl DoublyNestedKind {
+    pub fn compress<'a, T: PcoNumeric + 'a>(self, values: impl Iterator<Item = &'a Vec<Vec<T>>>) -> anyhow::Result<Vec<u8>> {
+        let mut lengths = Vec::new();
+        let mut inner = Vec::new();
+        for value in values {
+            lengths.push(value.len() as u64);
+            inner.extend(value.iter().map(|v| v.iter().map(T::to_number).collect::<Vec<_>>()));
+        }
+        let length_bytes = pco::standalone::simple_compress(&lengths, &pco::ChunkConfig::default())?;
+        let inner_bytes = pco_compress_nested(inner)?;
+        let (length_bytes, inner_bytes) = (serde_bytes::Bytes::new(&length_bytes), serde_bytes::Bytes::new(&inner_bytes));
+        Ok(rmp_serde::to_vec(&(length_bytes, inner_bytes))?)
+    }
+
+    pub fn decompress<T: PcoNumeric + 'static>(self, bytes: &[u8]) -> anyhow::Result<Vec<Vec<Vec<T>>>> {
+        if is_zstd(bytes) {
+            let values = serde_decompress::<Vec<Vec<T::Number>>>(bytes);
+            return values.map(|v| v.map(|v| v.into_iter().map(|v| v.into_iter().map(T::from_number).collect()).collect())).collect();
+        }
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (length_bytes, inner_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(bytes)?;
+        let lengths = pco::standalone::simple_decompress::<u64>(&length_bytes)?;
+        let mut inner = pco_decompress_nested::<T::Number>(inner_bytes)?.into_iter();
+        let mut values = Vec::with_capacity(lengths.len());
+        for length in lengths {
+            values.push(inner.by_ref().take(length as usize).map(|v| v.into_iter().map(T::from_number).collect()).collect());
+        }
+        Ok(values)
+    }
+
+    /// `float_round` only applies to single values and `Vec`s, so these are stored as they are
+    pub fn compress_round<'a, T: PcoNumeric + 'a>(
+        self, values: impl Iterator<Item = &'a Vec<Vec<T>>>, _round: FloatRound,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.compress(values)
+    }
+
+    pub fn decompress_round<T: PcoNumeric + 'static>(self, bytes: &[u8], _round: FloatRound) -> anyhow::Result<Vec<Vec<Vec<T>>>> {
+        self.decompress(bytes)
+    }
+
+    pub fn compare<V: PartialOrd>(self, a: &V, b: &V) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Each distinct key is serialized once with MessagePack into a dictionary. The number of entries in each map, the
+/// dictionary index of each key, and the values are then compressed with pco.
+impl MapKind {
+    pub fn compress<'a, M: PcoMap + 'a>(self, values: impl Iterator<Item = &'a M>) -> anyhow::Result<Vec<u8>> {
+        let mut dictionary = Vec::new();
+        let mut indexes: ahash::AHashMap<Vec<u8>, u32> = ahash::AHashMap::new();
+        let mut lengths = Vec::new();
+        let mut keys = Vec::new();
+        let mut numbers = Vec::new();
+        for map in values {
+            let mut length = 0u64;
+            for (key, value) in map.entries() {
+                let key = rmp_serde::to_vec(key)?;
+                let index = match indexes.get(&key) {
+                    Some(index) => *index,
+                    None => {
+                        let index = indexes.len() as u32;
+                        dictionary.extend_from_slice(&key);
+                        indexes.insert(key, index);
+                        index
+                    }
+                };
+                keys.push(index);
+                numbers.push(value.to_number());
+                length += 1;
+            }
+            lengths.push(length);
+        }
+        let config = pco::ChunkConfig::default();
+        let lengths = pco::standalone::simple_compress(&lengths, &config)?;
+        let keys = pco::standalone::simple_compress(&keys, &config)?;
+        let numbers = pco::standalone::simple_compress(&numbers, &config)?;
+        let parts = [&dictionary, &lengths, &keys, &numbers].map(|bytes| serde_bytes::Bytes::new(bytes));
+        Ok(rmp_serde::to_vec(&parts)?)
+    }
+
+    pub fn decompress<M: PcoMap + 'static>(self, bytes: &[u8]) -> anyhow::Result<Vec<M>> {
+        type Number<M> = <<M as PcoMap>::Value as PcoNumeric>::Number;
+        if is_zstd(bytes) {
+            let values = serde_decompress::<M::Legacy>(bytes);
+            return values
+                .map(|v| Ok(M::from_entries(v?.into_iter().map(|(key, value)| (key, <M::Value as PcoNumeric>::from_number(value))))))
+                .collect();
+        }
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (dictionary, lengths, keys, numbers): (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) = rmp_serde::from_slice(bytes)?;
+        let mut reader = dictionary.as_slice();
+        let mut dictionary = Vec::new();
+        while !reader.is_empty() {
+            dictionary.push(rmp_serde::from_read::<_, M::Key>(&mut reader)?);
+        }
+        let lengths = pco::standalone::simple_decompress::<u64>(&lengths)?;
+        let mut keys = pco::standalone::simple_decompress::<u32>(&keys)?.into_iter();
+        let mut numbers = pco::standalone::simple_decompress::<Number<M>>(&numbers)?.into_iter();
+        let mut maps = Vec::with_capacity(lengths.len());
+        for length in lengths {
+            let mut entries = Vec::with_capacity(length as usize);
+            for (key, number) in keys.by_ref().zip(numbers.by_ref()).take(length as usize) {
+                let key = dictionary.get(key as usize).ok_or_else(|| anyhow::Error::msg("map key not found in dictionary"))?;
+                entries.push((key.clone(), <M::Value as PcoNumeric>::from_number(number)));
+            }
+            maps.push(M::from_entries(entries.into_iter()));
+        }
+        Ok(maps)
+    }
+
+    /// `float_round` only applies to single values and `Vec`s, so map values are stored as they are
+    pub fn compress_round<'a, M: PcoMap + 'a>(self, values: impl Iterator<Item = &'a M>, _round: FloatRound) -> anyhow::Result<Vec<u8>> {
+        self.compress(values)
+    }
+
+    pub fn decompress_round<M: PcoMap + 'static>(self, bytes: &[u8], _round: FloatRound) -> anyhow::Result<Vec<M>> {
+        self.decompress(bytes)
+    }
+
+    pub fn compare<V: PartialOrd>(self, a: &V, b: &V) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl SerdeKind {
+    pub fn compress<'a, T: serde::Serialize + 'a>(self, values: impl Iterator<Item = &'a T>) -> anyhow::Result<Vec<u8>> {
+        serde_compress(values.collect())
+    }
+
+    pub fn decompress<T: serde::de::DeserializeOwned + 'static>(self, bytes: &[u8]) -> anyhow::Result<Values<'_, T>> {
+        if bytes.is_empty() {
+            return Ok(Values::from(Vec::new()));
+        }
+        let values: Box<dyn Iterator<Item = anyhow::Result<T>> + '_> = Box::new(serde_decompress(bytes));
+        Ok(Values::Streamed(values.peekable()))
+    }
+
+    pub fn compress_round<'a, T: serde::Serialize + 'a>(self, values: impl Iterator<Item = &'a T>, _round: FloatRound) -> anyhow::Result<Vec<u8>> {
+        self.compress(values)
+    }
+
+    pub fn decompress_round<T: serde::de::DeserializeOwned + 'static>(self, bytes: &[u8], _round: FloatRound) -> anyhow::Result<Values<'_, T>> {
+        self.decompress(bytes)
+    }
+
+    pub fn compare<V: PartialOrd>(self, a: &V, b: &V) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
@@ -0,0 +1,67 @@
+//! Deserialization of time ranges in the generated filters
+
+/// Deserializes many different time range formats:
+/// - an array with two strings becomes a normal time range: ["a", "b"] -> a..=b
+/// - an array with one string becomes a single-value time range: ["a"] -> a..=a
+/// - a string literal becomes a single-value time range:           "a" -> a..=a
+///
+/// Values are deserialized as `W`, and then converted into `T`.
+pub fn deserialize_time_range<'de, D, T, W>(deserializer: D) -> Result<Option<std::ops::RangeInclusive<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Copy,
+    W: serde::de::DeserializeOwned + Into<T>,
+{
+    deserializer.deserialize_any(TimeRangeVisitor::<T, W>(std::marker::PhantomData))
+}
+
+struct TimeRangeVisitor<T, W>(std::marker::PhantomData<(T, W)>);
+impl<'de, T, W> serde::de::Visitor<'de> for TimeRangeVisitor<T, W>
+where
+    T: Copy,
+    W: serde::de::DeserializeOwned + Into<T>,
+{
+    type Value = Option<std::ops::RangeInclusive<T>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a single time string or an array with 1-2 time strings")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if value.is_empty() {
+            return Ok(None);
+        }
+        match <W as serde::Deserialize>::deserialize(serde::de::value::StrDeserializer::<E>::new(value)) {
+            Ok(start) => {
+                let start = start.into();
+                Ok(Some(start..=start))
+            }
+            Err(err) => Err(E::custom("invalid time format: ".to_string() + err.to_string().as_str())),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let start: T = match seq.next_element::<Option<W>>()? {
+            Some(Some(time)) => time.into(),
+            Some(None) | None => return Ok(None),
+        };
+        let end = match seq.next_element::<Option<W>>()? {
+            Some(Some(time)) => time.into(),
+            Some(None) | None => start,
+        };
+        Ok(Some(start..=end))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(None)
+    }
+}
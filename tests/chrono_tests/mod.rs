@@ -15,8 +15,8 @@ async fn systemtime_chrono_interop() {
         pub collected_at: SystemTime,
     }
     let database_id = 1;
-    let start = ymd_hms_micros(2026, 01, 01, 1, 01, 02, 345_678).unwrap();
-    let end = ymd_hms_micros(2026, 01, 01, 5, 03, 04, 567_890).unwrap();
+    let start = ymd_hms_micros(2026, 1, 1, 1, 1, 2, 345_678).unwrap();
+    let end = ymd_hms_micros(2026, 1, 1, 5, 3, 4, 567_890).unwrap();
     let db = &super::DB_POOL.get().await.unwrap();
     let sql = "
         DROP TABLE IF EXISTS query_stats;
@@ -31,9 +31,9 @@ async fn systemtime_chrono_interop() {
     ";
     db.batch_execute(sql).await.unwrap();
 
-    let time_1_23_45 = ymd_hms_micros(2026, 01, 01, 1, 23, 45, 123_456).unwrap();
-    let time_2_34_56 = ymd_hms_micros(2026, 01, 01, 2, 34, 56, 789_012).unwrap();
-    let time_3_45_00 = ymd_hms_micros(2026, 01, 01, 3, 45, 00, 345_678).unwrap();
+    let time_1_23_45 = ymd_hms_micros(2026, 1, 1, 1, 23, 45, 123_456).unwrap();
+    let time_2_34_56 = ymd_hms_micros(2026, 1, 1, 2, 34, 56, 789_012).unwrap();
+    let time_3_45_00 = ymd_hms_micros(2026, 1, 1, 3, 45, 00, 345_678).unwrap();
 
     // Write, using SystemTime.
     let collected_at = time_1_23_45.into();
@@ -46,7 +46,7 @@ async fn systemtime_chrono_interop() {
     // Read
     {
         let mut actual: Vec<QueryStat> = vec![];
-        let filter = Filter::new(&[database_id], start.into()..=end.into());
+        let filter = QueryStatFilter::new(&[database_id], start.into()..=end.into());
         for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
             for stat in group.decompress().unwrap() {
                 actual.push(stat.clone());
@@ -54,8 +54,8 @@ async fn systemtime_chrono_interop() {
         }
         assert_eq!(actual.len(), 2);
 
-        assert_eq!(actual[0].collected_at, time_1_23_45.into());
-        assert_eq!(actual[1].collected_at, time_2_34_56.into());
+        assert_eq!(actual[0].collected_at, SystemTime::from(time_1_23_45));
+        assert_eq!(actual[1].collected_at, SystemTime::from(time_2_34_56));
     }
 
     {
@@ -69,7 +69,7 @@ async fn systemtime_chrono_interop() {
 
         // Read, using chrono::DateTime.
         let mut actual: Vec<QueryStat> = vec![];
-        let filter = Filter::new(&[database_id], start..=end);
+        let filter = QueryStatFilter::new(&[database_id], start..=end);
         for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
             for stat in group.decompress().unwrap() {
                 actual.push(stat.clone());
@@ -89,7 +89,7 @@ async fn systemtime_chrono_interop() {
 
     // Read again, using SystemTime.
     let mut actual: Vec<QueryStat> = vec![];
-    let filter = Filter::new(&[database_id], start.into()..=end.into());
+    let filter = QueryStatFilter::new(&[database_id], start.into()..=end.into());
     for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             actual.push(stat.clone());
@@ -98,7 +98,7 @@ async fn systemtime_chrono_interop() {
 
     assert_eq!(actual.len(), 3);
 
-    assert_eq!(actual[0].collected_at, time_1_23_45.into());
-    assert_eq!(actual[1].collected_at, time_2_34_56.into());
-    assert_eq!(actual[2].collected_at, time_3_45_00.into());
+    assert_eq!(actual[0].collected_at, SystemTime::from(time_1_23_45));
+    assert_eq!(actual[1].collected_at, SystemTime::from(time_2_34_56));
+    assert_eq!(actual[2].collected_at, SystemTime::from(time_3_45_00));
 }
@@ -0,0 +1,13 @@
+#[pco_store::store(timestamp = collected_at, group_by = [database_id, collected_at])]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+}
+
+#[pco_store::store(timestamp = collected_at, group_by = [database_id, database_id])]
+pub struct QueryRun {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+}
+
+fn main() {}
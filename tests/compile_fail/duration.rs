@@ -0,0 +1,11 @@
+#[allow(unused_imports)]
+use std::time::{Duration, SystemTime};
+
+#[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+pub struct QueryRun {
+    pub database_id: i64,
+    pub collected_at: SystemTime,
+    pub runtime: Duration,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+#[pco_store::flatten]
+pub struct IoStats {
+    pub read_time: f64,
+    #[pco(flatten)]
+    pub nested: Nested,
+}
+
+#[pco_store::flatten]
+pub struct Nested {
+    pub blocks: i64,
+}
+
+#[pco_store::store(timestamp = collected_at, group_by = [nested_blocks])]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+    #[pco(flatten)]
+    pub nested: Nested,
+}
+
+#[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+pub struct QueryRun {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+    #[pco(flatten)]
+    pub io: NotFlattened,
+}
+
+pub struct NotFlattened {
+    pub read_time: f64,
+}
+
+#[pco_store::flatten(prefix = io)]
+pub struct WithArguments {
+    pub read_time: f64,
+}
+
+fn main() {}
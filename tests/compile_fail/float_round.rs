@@ -0,0 +1,19 @@
+#[pco_store::store(group_by = [database_id], float_round = 0)]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub total_time: f64,
+}
+
+#[pco_store::store(group_by = [database_id], float_round = 10)]
+pub struct QueryRun {
+    pub database_id: i64,
+    pub total_time: f64,
+}
+
+#[pco_store::store(group_by = [database_id], float_round = "2")]
+pub struct QuerySample {
+    pub database_id: i64,
+    pub total_time: f64,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[pco_store::store(group_by = [database_id])]
+pub struct QueryStat<T> {
+    pub database_id: i64,
+    pub calls: T,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[pco_store::store(timestamp = collected_at group_by = [database_id])]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+    pub calls: i64,
+}
+
+fn main() {}
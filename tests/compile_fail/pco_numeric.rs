@@ -0,0 +1,15 @@
+pub struct Total(i128);
+
+impl pco_store::PcoNumeric for Total {
+    type Number = i128;
+
+    fn to_number(&self) -> i128 {
+        self.0
+    }
+
+    fn from_number(number: i128) -> Self {
+        Self(number)
+    }
+}
+
+fn main() {}
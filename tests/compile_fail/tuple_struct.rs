@@ -0,0 +1,4 @@
+#[pco_store::store(group_by = [database_id])]
+pub struct QueryStat(i64, i64);
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[pco_store::store(timestamp = collected_at, group_bye = [database_id])]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub collected_at: std::time::SystemTime,
+    pub calls: i64,
+}
+
+#[pco_store::store(group_by = [database_id], float_round = 2, float_round = auto)]
+pub struct QueryRun {
+    pub database_id: i64,
+    pub total_time: f64,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+#[pco_store::store(group_by = [database_id, active])]
+pub struct QueryStat {
+    pub database_id: i64,
+    pub active: Vec<i64>,
+    pub calls: i64,
+}
+
+#[pco_store::store(group_by = [database_id])]
+pub struct QueryRun {
+    pub database_id: i64,
+    #[pco(sql_type = "int8")]
+    pub calls: i64,
+}
+
+pub struct Date(i32);
+
+#[pco_store::store(group_by = [database_id, day])]
+pub struct QueryDay {
+    pub database_id: i64,
+    pub day: Date,
+    pub calls: i64,
+}
+
+#[pco_store::store(group_by = [database_id])]
+pub struct QueryOption {
+    pub database_id: Option<i64>,
+    pub calls: i64,
+}
+
+fn main() {}
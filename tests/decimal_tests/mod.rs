@@ -0,0 +1,94 @@
+use super::DB_POOL;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+const SQL: &str = "
+    DROP TABLE IF EXISTS costs;
+    CREATE TABLE costs (
+        database_id bigint NOT NULL,
+        start_at timestamptz NOT NULL,
+        end_at timestamptz NOT NULL,
+        collected_at bytea STORAGE EXTERNAL NOT NULL,
+        amount bytea STORAGE EXTERNAL NOT NULL,
+        rate bytea STORAGE EXTERNAL NOT NULL
+    );
+";
+
+#[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cost {
+    pub database_id: i64,
+    pub collected_at: SystemTime,
+    #[pco(scale = 2)]
+    pub amount: Decimal,
+    pub rate: Decimal,
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn scale() -> anyhow::Result<()> {
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = SystemTime::UNIX_EPOCH;
+    let d = |s: &str| Decimal::from_str(s).unwrap();
+    let costs = vec![
+        Cost { database_id: 1, collected_at: t, amount: d("12.34"), rate: d("0.000123") },
+        Cost { database_id: 1, collected_at: t, amount: d("-0.5"), rate: d("1") },
+        Cost { database_id: 1, collected_at: t, amount: d("1.005"), rate: d("2.5") },
+        Cost { database_id: 1, collected_at: t, amount: d("0.125"), rate: d("1") },
+        Cost { database_id: 1, collected_at: t, amount: d("-0.125"), rate: d("1") },
+    ];
+    CompressedCosts::store(db, costs.clone()).await?;
+
+    // Values are stored exactly at the given scale, rounding any extra decimal places with midpoints away from zero
+    let group = CompressedCosts::load(db, CostFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert!(pco::standalone::simple_decompress::<i64>(&group.amount).is_ok());
+    let actual = group.decompress()?;
+    assert_eq!(actual.iter().map(|c| c.amount.to_string()).collect::<Vec<_>>(), ["12.34", "-0.50", "1.01", "0.13", "-0.13"]);
+    assert_eq!(actual.iter().map(|c| c.rate).collect::<Vec<_>>(), costs.iter().map(|c| c.rate).collect::<Vec<_>>());
+
+    // Values that don't fit in an `i64` at the given scale are an error
+    let cost = Cost { database_id: 1, collected_at: t, amount: Decimal::MAX, rate: d("1") };
+    let err = CompressedCosts::store(db, vec![cost]).await.unwrap_err();
+    assert_eq!(err.to_string(), format!("amount can't be stored with scale 2: {}", Decimal::MAX));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn scale_added() -> anyhow::Result<()> {
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    // Data written with MessagePack before `scale` was added is read as it was written
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], table_name = costs)]
+    pub struct CostBefore {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub amount: Decimal,
+        pub rate: Decimal,
+    }
+    let t = SystemTime::UNIX_EPOCH;
+    let d = |s: &str| Decimal::from_str(s).unwrap();
+    let before = vec![
+        CostBefore { database_id: 1, collected_at: t, amount: d("12.34"), rate: d("1") },
+        CostBefore { database_id: 1, collected_at: t, amount: d("0.125"), rate: d("2.5") },
+    ];
+    CompressedCostBefores::store(db, before).await?;
+    let group = CompressedCosts::load(db, CostFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert!(pco::standalone::simple_decompress::<i64>(&group.amount).is_err());
+    let actual = group.decompress()?;
+    assert_eq!(actual.iter().map(|c| c.amount.to_string()).collect::<Vec<_>>(), ["12.34", "0.125"]);
+
+    // Rewriting the data stores it at the new scale
+    let mut costs = Vec::new();
+    for group in CompressedCosts::delete(db, CostFilter::new(&[1], t..=t), ()).await? {
+        costs.extend(group.decompress()?);
+    }
+    CompressedCosts::store(db, costs).await?;
+    let group = CompressedCosts::load(db, CostFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.amount)?, vec![1234, 13]);
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use serde::Deserialize as _;
 pub struct QueryStat {
     pub database_id: i64,
     pub calls: i64,
@@ -6,220 +5,46 @@ pub struct QueryStat {
 }
 /// Generated by pco_store to store and load compressed versions of [QueryStat]
 pub struct CompressedQueryStats {
-    filter: Option<Filter>,
+    filter: Option<QueryStatFilter>,
     database_id: Vec<u8>,
     calls: Vec<u8>,
     total_time: Vec<u8>,
 }
-impl CompressedQueryStats {
-    /// Loads data for the specified filters.
-    pub async fn load(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedQueryStats>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM "
-            + "query_stats" + " WHERE " + "true";
-        let mut results = Vec::new();
-        for row in db.query(&db.prepare_cached(&sql).await?, &[]).await? {
-            results.push(fields.load_from_row(row, Some(filter.clone()))?);
-        }
-        Ok(results)
-    }
-    /// Deletes data for the specified filters, returning it to the caller.
-    ///
-    /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
-    pub async fn delete(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedQueryStats>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        let sql = "DELETE FROM ".to_string() + "query_stats" + " WHERE " + "true"
-            + " RETURNING " + fields.select().as_str();
-        let mut results = Vec::new();
-        for row in db.query(&db.prepare_cached(&sql).await?, &[]).await? {
-            results.push(fields.load_from_row(row, None)?);
-        }
-        Ok(results)
-    }
-    /// Decompresses a group of data points.
-    pub fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
-        let mut results = Vec::new();
-        let database_id: Vec<i64> = if self.database_id.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.database_id)?
-        };
-        let calls: Vec<i64> = if self.calls.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.calls)?
-        };
-        let total_time: Vec<f64> = if self.total_time.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.total_time)?
-        };
-        let len = [database_id.len(), calls.len(), total_time.len()]
-            .into_iter()
-            .max()
-            .unwrap_or(0);
-        for index in 0..len {
-            let row = QueryStat {
-                database_id: database_id.get(index).cloned().unwrap_or_default(),
-                calls: calls.get(index).cloned().unwrap_or_default(),
-                total_time: total_time.get(index).cloned().unwrap_or_default(),
-            };
-            if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
-                results.push(row);
-            }
-        }
-        Ok(results)
-    }
-    /// Writes the data to disk.
-    pub async fn store(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<QueryStat>,
-    ) -> anyhow::Result<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows.entry(()).or_default().push(row);
-        }
-        let sql = "COPY query_stats (database_id, calls, total_time) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.database_id).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-    /// Writes the data to disk, with the provided grouping closure applied.
-    ///
-    /// This can be used to improve the compression ratio and reduce read IO, for example
-    /// by compacting real-time data into a single row per hour / day / week.
-    pub async fn store_grouped<F, R>(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<QueryStat>,
-        grouping: F,
-    ) -> anyhow::Result<()>
-    where
-        F: Fn(&QueryStat) -> R,
-        R: Eq + std::hash::Hash,
-    {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows.entry((grouping(&row))).or_default().push(row);
-        }
-        let sql = "COPY query_stats (database_id, calls, total_time) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.database_id).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-}
-#[serde(deny_unknown_fields)]
+#[serde(crate = "::pco_store::__private::serde", deny_unknown_fields)]
 /// Generated by pco_store to specify filters when loading [QueryStat]
-pub struct Filter {
+pub struct QueryStatFilter {
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub database_id: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub calls: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub total_time: Vec<f64>,
 }
 #[automatically_derived]
-impl ::core::fmt::Debug for Filter {
+impl ::core::fmt::Debug for QueryStatFilter {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         ::core::fmt::Formatter::debug_struct_field3_finish(
             f,
-            "Filter",
+            "QueryStatFilter",
             "database_id",
             &self.database_id,
             "calls",
@@ -230,10 +55,10 @@ impl ::core::fmt::Debug for Filter {
     }
 }
 #[automatically_derived]
-impl ::core::default::Default for Filter {
+impl ::core::default::Default for QueryStatFilter {
     #[inline]
-    fn default() -> Filter {
-        Filter {
+    fn default() -> QueryStatFilter {
+        QueryStatFilter {
             database_id: ::core::default::Default::default(),
             calls: ::core::default::Default::default(),
             total_time: ::core::default::Default::default(),
@@ -248,10 +73,9 @@ impl ::core::default::Default for Filter {
     clippy::absolute_paths,
 )]
 const _: () = {
-    #[allow(unused_extern_crates, clippy::useless_attribute)]
-    extern crate serde as _serde;
+    use ::pco_store::__private::serde as _serde;
     #[automatically_derived]
-    impl<'de> _serde::Deserialize<'de> for Filter {
+    impl<'de> _serde::Deserialize<'de> for QueryStatFilter {
         fn deserialize<__D>(
             __deserializer: __D,
         ) -> _serde::__private228::Result<Self, __D::Error>
@@ -357,19 +181,19 @@ const _: () = {
             }
             #[doc(hidden)]
             struct __Visitor<'de> {
-                marker: _serde::__private228::PhantomData<Filter>,
+                marker: _serde::__private228::PhantomData<QueryStatFilter>,
                 lifetime: _serde::__private228::PhantomData<&'de ()>,
             }
             #[automatically_derived]
             impl<'de> _serde::de::Visitor<'de> for __Visitor<'de> {
-                type Value = Filter;
+                type Value = QueryStatFilter;
                 fn expecting(
                     &self,
                     __formatter: &mut _serde::__private228::Formatter,
                 ) -> _serde::__private228::fmt::Result {
                     _serde::__private228::Formatter::write_str(
                         __formatter,
-                        "struct Filter",
+                        "struct QueryStatFilter",
                     )
                 }
                 #[inline]
@@ -384,7 +208,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -396,9 +220,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -422,7 +248,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -434,9 +260,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -460,7 +288,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<f64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -472,9 +300,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -494,7 +324,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(QueryStatFilter {
                         database_id: __field0,
                         calls: __field1,
                         total_time: __field2,
@@ -527,7 +357,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -540,9 +370,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -570,7 +402,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -583,9 +415,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -615,7 +449,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<f64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -628,9 +462,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -668,7 +504,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(QueryStatFilter {
                         database_id: __field0,
                         calls: __field1,
                         total_time: __field2,
@@ -683,10 +519,10 @@ const _: () = {
             ];
             _serde::Deserializer::deserialize_struct(
                 __deserializer,
-                "Filter",
+                "QueryStatFilter",
                 FIELDS,
                 __Visitor {
-                    marker: _serde::__private228::PhantomData::<Filter>,
+                    marker: _serde::__private228::PhantomData::<QueryStatFilter>,
                     lifetime: _serde::__private228::PhantomData,
                 },
             )
@@ -694,10 +530,10 @@ const _: () = {
     }
 };
 #[automatically_derived]
-impl ::core::clone::Clone for Filter {
+impl ::core::clone::Clone for QueryStatFilter {
     #[inline]
-    fn clone(&self) -> Filter {
-        Filter {
+    fn clone(&self) -> QueryStatFilter {
+        QueryStatFilter {
             database_id: ::core::clone::Clone::clone(&self.database_id),
             calls: ::core::clone::Clone::clone(&self.calls),
             total_time: ::core::clone::Clone::clone(&self.total_time),
@@ -705,52 +541,41 @@ impl ::core::clone::Clone for Filter {
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Filter {}
+impl ::core::marker::StructuralPartialEq for QueryStatFilter {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Filter {
+impl ::core::cmp::PartialEq for QueryStatFilter {
     #[inline]
-    fn eq(&self, other: &Filter) -> bool {
+    fn eq(&self, other: &QueryStatFilter) -> bool {
         self.database_id == other.database_id && self.calls == other.calls
             && self.total_time == other.total_time
     }
 }
-impl Filter {
-    /// Builds new filter with the required fields defined by `group_by` and `timestamp`
-    pub fn new() -> Self {
-        Self { ..Self::default() }
-    }
-    fn matches(&self, row: &QueryStat) -> bool {
-        (self.database_id.is_empty() || self.database_id.contains(&row.database_id))
-            && (self.calls.is_empty() || self.calls.contains(&row.calls))
-            && (self.total_time.is_empty() || self.total_time.contains(&row.total_time))
-    }
-}
 /// Generated by pco_store to choose which fields to decompress when loading [QueryStat]
-pub struct Fields {
+pub struct QueryStatFields {
     database_id: bool,
     calls: bool,
     total_time: bool,
 }
 #[automatically_derived]
 #[doc(hidden)]
-unsafe impl ::core::clone::TrivialClone for Fields {}
+unsafe impl ::core::clone::TrivialClone for QueryStatFields {}
 #[automatically_derived]
-impl ::core::clone::Clone for Fields {
+impl ::core::clone::Clone for QueryStatFields {
     #[inline]
-    fn clone(&self) -> Fields {
+    fn clone(&self) -> QueryStatFields {
         let _: ::core::clone::AssertParamIsClone<bool>;
         *self
     }
 }
 #[automatically_derived]
-impl ::core::marker::Copy for Fields {}
+impl ::core::marker::Copy for QueryStatFields {}
 #[automatically_derived]
-impl ::core::fmt::Debug for Fields {
+impl ::core::fmt::Debug for QueryStatFields {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         ::core::fmt::Formatter::debug_struct_field3_finish(
             f,
-            "Fields",
+            "QueryStatFields",
             "database_id",
             &self.database_id,
             "calls",
@@ -761,214 +586,549 @@ impl ::core::fmt::Debug for Fields {
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Fields {}
+impl ::core::marker::StructuralPartialEq for QueryStatFields {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Fields {
+impl ::core::cmp::PartialEq for QueryStatFields {
     #[inline]
-    fn eq(&self, other: &Fields) -> bool {
+    fn eq(&self, other: &QueryStatFields) -> bool {
         self.database_id == other.database_id && self.calls == other.calls
             && self.total_time == other.total_time
     }
 }
-impl Fields {
-    pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
-        fields.try_into().map_err(|e| anyhow::Error::msg(e))
-    }
-    pub fn required() -> Self {
-        Self {
-            database_id: false,
-            calls: false,
-            total_time: false,
+const _: () = {
+    #[allow(unused_imports)]
+    use ::pco_store::__private::{
+        ahash, anyhow, deadpool_postgres, futures, pco, rmp_serde, serde, serde_bytes,
+        serde_with, tokio, tokio_postgres, zstd,
+    };
+    use ::pco_store::__private::*;
+    type Filter = QueryStatFilter;
+    type Fields = QueryStatFields;
+    impl CompressedQueryStats {
+        /// Loads data for the specified filters.
+        pub async fn load(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedQueryStats>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM "
+                + "query_stats" + " WHERE " + "true";
+            let mut results = Vec::new();
+            for row in db.query(&db.prepare_cached(&sql).await?, &[]).await? {
+                results.push(fields.load_from_row(row, Some(filter.clone()))?);
+            }
+            Ok(results)
         }
-    }
-    fn merge_filter(&mut self, filter: &Filter) {
-        (!filter.database_id.is_empty()).then(|| self.database_id = true);
-        (!filter.calls.is_empty()).then(|| self.calls = true);
-        (!filter.total_time.is_empty()).then(|| self.total_time = true);
-    }
-    fn select(&self) -> String {
-        let mut fields = Vec::new();
-        self.database_id.then(|| fields.push("database_id"));
-        self.calls.then(|| fields.push("calls"));
-        self.total_time.then(|| fields.push("total_time"));
-        fields.join(", ")
-    }
-    fn load_from_row(
-        &self,
-        row: tokio_postgres::Row,
-        filter: Option<Filter>,
-    ) -> anyhow::Result<CompressedQueryStats> {
-        let mut index = 0;
-        Ok(CompressedQueryStats {
-            filter,
-            database_id: if self.database_id {
-                let v = row.get(index);
-                index += 1;
-                v
+        /// Deletes data for the specified filters, returning it to the caller.
+        ///
+        /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
+        pub async fn delete(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedQueryStats>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            let sql = "DELETE FROM ".to_string() + "query_stats" + " WHERE " + "true"
+                + " RETURNING " + fields.select().as_str();
+            let mut results = Vec::new();
+            for row in db.query(&db.prepare_cached(&sql).await?, &[]).await? {
+                results.push(fields.load_from_row(row, None)?);
+            }
+            Ok(results)
+        }
+        /// Decompresses a group of data points.
+        pub fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
+            let mut results = Vec::new();
+            let database_id: Vec<i64> = if self.database_id.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            calls: if self.calls {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.database_id)?
+            };
+            let calls: Vec<i64> = if self.calls.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            total_time: if self.total_time {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.calls)?
+            };
+            let total_time: Vec<f64> = if self.total_time.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-        })
-    }
-}
-impl Default for Fields {
-    fn default() -> Self {
-        Self {
-            database_id: true,
-            calls: true,
-            total_time: true,
+                pco::standalone::simple_decompress(&self.total_time)?
+            };
+            let len = [
+                Some(database_id.len()),
+                Some(calls.len()),
+                Some(total_time.len()),
+            ]
+                .into_iter()
+                .flatten()
+                .max()
+                .filter(|len| *len > 0);
+            let mut index = 0;
+            while len.map_or_else(|| [].contains(&true), |len| index < len) {
+                let row = QueryStat {
+                    database_id: database_id.get(index).cloned().unwrap_or_default(),
+                    calls: calls.get(index).cloned().unwrap_or_default(),
+                    total_time: total_time.get(index).cloned().unwrap_or_default(),
+                };
+                if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
+                    results.push(row);
+                }
+                index += 1;
+            }
+            Ok(results)
         }
-    }
-}
-impl TryFrom<&[&str]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
-        let mut fields = Self::required();
-        for s in input {
-            match *s {
-                "database_id" => fields.database_id = true,
-                "calls" => fields.calls = true,
-                "total_time" => fields.total_time = true,
-                _ => return Err("unknown field"),
+        /// Decompresses many groups in parallel, returning their data points in the same order as the groups.
+        pub async fn decompress_all(
+            groups: Vec<Self>,
+        ) -> anyhow::Result<Vec<QueryStat>> {
+            if groups.is_empty() {
+                return Ok(Vec::new());
             }
+            let threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let chunk_size = groups.len().div_ceil(threads);
+            let mut groups = groups.into_iter();
+            let mut tasks = Vec::new();
+            loop {
+                let chunk: Vec<Self> = groups.by_ref().take(chunk_size).collect();
+                if chunk.is_empty() {
+                    break;
+                }
+                tasks
+                    .push(
+                        tokio::task::spawn_blocking(move || {
+                            chunk
+                                .into_iter()
+                                .map(Self::decompress)
+                                .collect::<anyhow::Result<Vec<_>>>()
+                        }),
+                    );
+            }
+            let mut groups: Vec<Vec<QueryStat>> = Vec::new();
+            for task in tasks {
+                groups.extend(task.await??);
+            }
+            Ok(groups.into_iter().flatten().collect())
+        }
+        /// Compresses the fields of a group, returning the rows along with their timestamp range.
+        fn compress_group(
+            rows: Vec<QueryStat>,
+        ) -> anyhow::Result<(Vec<QueryStat>, (i64, i64), Vec<Vec<u8>>)> {
+            let bounds = (0, 0);
+            let compressed: Vec<Vec<u8>> = <[_]>::into_vec(
+                ::alloc::boxed::box_new([
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.database_id).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                ]),
+            );
+            Ok((rows, bounds, compressed))
+        }
+        /// Compresses each part of the data that will be written as a row.
+        async fn compress_parts(
+            parts: Vec<Vec<QueryStat>>,
+        ) -> anyhow::Result<Vec<(Vec<QueryStat>, (i64, i64), Vec<Vec<u8>>)>> {
+            parts.into_iter().map(Self::compress_group).collect()
+        }
+        /// Writes the data to disk.
+        pub async fn store(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = QueryStat>,
+        ) -> anyhow::Result<()> {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows.entry(()).or_default().push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY query_stats (database_id, calls, total_time) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk, with the provided grouping closure applied.
+        ///
+        /// This can be used to improve the compression ratio and reduce read IO, for example
+        /// by compacting real-time data into a single row per hour / day / week.
+        pub async fn store_grouped<F, R>(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = QueryStat>,
+            grouping: F,
+        ) -> anyhow::Result<()>
+        where
+            F: Fn(&QueryStat) -> R,
+            R: Eq + std::hash::Hash,
+        {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows.entry((grouping(&row))).or_default().push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY query_stats (database_id, calls, total_time) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk as it's received from a stream, for bulk imports that don't fit in memory.
+        ///
+        /// Rows are grouped the same way as [store][Self::store], and each group is written once it has `group_size`
+        /// rows. Any remaining groups are written when the stream ends. Duplicates are only removed within a group.
+        pub async fn store_stream(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl futures::Stream<Item = QueryStat>,
+            group_size: usize,
+        ) -> anyhow::Result<()> {
+            if group_size == 0 {
+                return Err(anyhow::Error::msg("group_size must be greater than zero"));
+            }
+            let sql = "COPY query_stats (database_id, calls, total_time) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let mut rows = rows;
+            #[allow(unused_mut)]
+            let mut rows = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut rows)
+            };
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            while let Some(row) = futures::StreamExt::next(&mut rows).await {
+                let group = grouped_rows.entry(()).or_default();
+                group.push(row);
+                if group.len() >= group_size {
+                    let rows = std::mem::take(group);
+                    let groups = std::iter::once(rows);
+                    let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+                    while !parts.is_empty() {
+                        for (rows, bounds, compressed) in Self::compress_parts(
+                                std::mem::take(&mut parts),
+                            )
+                            .await?
+                        {
+                            writer
+                                .as_mut()
+                                .write(
+                                    &[
+                                        &compressed[0usize],
+                                        &compressed[1usize],
+                                        &compressed[2usize],
+                                    ],
+                                )
+                                .await?;
+                        }
+                    }
+                }
+            }
+            let groups = grouped_rows
+                .into_values()
+                .filter(|rows| !rows.is_empty())
+                .map(|rows| { rows });
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
         }
-        Ok(fields)
     }
-}
-impl<const N: usize> TryFrom<&[&str; N]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
-        Self::try_from(&input[..])
+    impl ::pco_store::PcoStore for CompressedQueryStats {
+        type Row = QueryStat;
+        type Filter = Filter;
+        type Fields = Fields;
+        const TABLE_NAME: &'static str = "query_stats";
+        const COLUMNS: &'static [&'static str] = &["database_id", "calls", "total_time"];
+        const GROUP_BY: &'static [&'static str] = &[];
+        const TIMESTAMP: Option<&'static str> = None;
+        fn store<D>(
+            db: &D,
+            rows: impl IntoIterator<Item = QueryStat> + Send,
+        ) -> impl std::future::Future<Output = anyhow::Result<()>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::store(db, rows)
+        }
+        fn load<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::load(db, filter, fields)
+        }
+        fn delete<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::delete(db, filter, fields)
+        }
+        fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
+            Self::decompress(self)
+        }
+        fn decompress_all(
+            groups: Vec<Self>,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<QueryStat>>> + Send {
+            Self::decompress_all(groups)
+        }
     }
-}
-impl TryFrom<Vec<String>> for Fields {
-    type Error = &'static str;
-    fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
-        let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
-        Self::try_from(input.as_slice())
+    impl Filter {
+        /// Builds new filter with the required fields defined by `group_by` and `timestamp`
+        pub fn new() -> Self {
+            Self { ..Self::default() }
+        }
+        fn matches(&self, row: &QueryStat) -> bool {
+            (self.database_id.is_empty() || self.database_id.contains(&row.database_id))
+                && (self.calls.is_empty() || self.calls.contains(&row.calls))
+                && (self.total_time.is_empty()
+                    || self.total_time.contains(&row.total_time))
+        }
     }
-}
-impl From<()> for Fields {
-    fn from(_: ()) -> Self {
-        Self::default()
+    impl Fields {
+        pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
+            fields.try_into().map_err(|e| anyhow::Error::msg(e))
+        }
+        pub fn required() -> Self {
+            Self {
+                database_id: false,
+                calls: false,
+                total_time: false,
+            }
+        }
+        fn merge_filter(&mut self, filter: &Filter) {
+            (!filter.database_id.is_empty()).then(|| self.database_id = true);
+            (!filter.calls.is_empty()).then(|| self.calls = true);
+            (!filter.total_time.is_empty()).then(|| self.total_time = true);
+        }
+        fn select(&self) -> String {
+            let mut fields = Vec::new();
+            self.database_id.then(|| fields.push("database_id"));
+            self.calls.then(|| fields.push("calls"));
+            self.total_time.then(|| fields.push("total_time"));
+            fields.join(", ")
+        }
+        fn load_from_row(
+            &self,
+            row: tokio_postgres::Row,
+            filter: Option<Filter>,
+        ) -> anyhow::Result<CompressedQueryStats> {
+            let mut index = 0;
+            Ok(CompressedQueryStats {
+                filter,
+                database_id: if self.database_id {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                calls: if self.calls {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                total_time: if self.total_time {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+            })
+        }
     }
-}
-impl<'de> serde::Deserialize<'de> for Fields {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_any(FieldsVisitor)
+    impl Default for Fields {
+        fn default() -> Self {
+            Self {
+                database_id: true,
+                calls: true,
+                total_time: true,
+            }
+        }
     }
-}
-struct FieldsVisitor;
-impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
-    type Value = Fields;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("an array of strings matching the struct fields")
+    impl TryFrom<&[&str]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
+            let mut fields = Self::required();
+            for s in input {
+                match *s {
+                    "database_id" => fields.database_id = true,
+                    "calls" => fields.calls = true,
+                    "total_time" => fields.total_time = true,
+                    _ => return Err("unknown field"),
+                }
+            }
+            Ok(fields)
+        }
     }
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let mut fields = Vec::new();
-        while let Some(field) = seq.next_element()? {
-            fields.push(field);
-        }
-        Fields::try_from(fields).map_err(serde::de::Error::custom)
+    impl<const N: usize> TryFrom<&[&str; N]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
+            Self::try_from(&input[..])
+        }
     }
-    fn visit_unit<E>(self) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(Fields::default())
+    impl TryFrom<Vec<String>> for Fields {
+        type Error = &'static str;
+        fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
+            let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
+            Self::try_from(input.as_slice())
+        }
     }
-}
-fn serde_compress<T>(items: Vec<T>) -> anyhow::Result<Vec<u8>>
-where
-    T: serde::Serialize,
-{
-    use std::io::Write;
-    let mut output = Vec::new();
-    let mut encoder = zstd::stream::write::Encoder::new(&mut output, 3)?;
-    for item in items {
-        rmp_serde::encode::write(&mut encoder, &item)?;
+    impl From<()> for Fields {
+        fn from(_: ()) -> Self {
+            Self::default()
+        }
     }
-    encoder.finish()?;
-    Ok(output)
-}
-fn serde_decompress<T>(input: &[u8]) -> impl Iterator<Item = anyhow::Result<T>> + '_
-where
-    T: for<'de> serde::Deserialize<'de> + 'static,
-{
-    let decoder = match zstd::stream::read::Decoder::new(input) {
-        Ok(d) => d,
-        Err(e) => {
-            return Box::new(std::iter::once(Err(e.into())))
-                as Box<dyn Iterator<Item = _>>;
+    impl<'de> serde::Deserialize<'de> for Fields {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(FieldsVisitor)
         }
-    };
-    let buffered = std::io::BufReader::with_capacity(128 * 1024, decoder);
-    let mut de = rmp_serde::decode::Deserializer::new(buffered);
-    Box::new(
-        std::iter::from_fn(move || match serde::Deserialize::deserialize(&mut de) {
-            Ok(item) => Some(Ok(item)),
-            Err(
-                rmp_serde::decode::Error::InvalidMarkerRead(ref e),
-            ) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
-            Err(e) => Some(Err(e.into())),
-        }),
-    )
-}
-fn pco_compress_nested<T>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>>
-where
-    T: ::pco::data_types::Number,
-{
-    let mut lengths = Vec::new();
-    let mut values = Vec::new();
-    for vals in nested_values {
-        lengths.push(vals.len() as u64);
-        values.extend(vals);
     }
-    let length_bytes = ::pco::standalone::simple_compress(
-        &lengths,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let value_bytes = ::pco::standalone::simple_compress(
-        &values,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let (length_bytes, value_bytes) = (
-        serde_bytes::Bytes::new(&length_bytes),
-        serde_bytes::Bytes::new(&value_bytes),
-    );
-    Ok(rmp_serde::to_vec(&(length_bytes, value_bytes))?)
-}
-fn pco_decompress_nested<T>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>>
-where
-    T: ::pco::data_types::Number,
-{
-    let (length_bytes, value_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(&bytes)?;
-    let lengths = ::pco::standalone::simple_decompress::<u64>(&length_bytes)?;
-    let values = ::pco::standalone::simple_decompress::<T>(&value_bytes)?;
-    let mut values = values.into_iter();
-    let mut nested_values = Vec::with_capacity(lengths.len());
-    for length in lengths {
-        nested_values.push(values.by_ref().take(length as usize).collect::<Vec<T>>());
+    struct FieldsVisitor;
+    impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+        type Value = Fields;
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an array of strings matching the struct fields")
+        }
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut fields = Vec::new();
+            while let Some(field) = seq.next_element()? {
+                fields.push(field);
+            }
+            Fields::try_from(fields).map_err(serde::de::Error::custom)
+        }
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(Fields::default())
+        }
     }
-    Ok(nested_values)
-}
+};
@@ -1,4 +1,3 @@
-use serde::Deserialize as _;
 pub struct QueryStat {
     pub database_id: i64,
     pub collected_at: chrono::DateTime,
@@ -14,7 +13,7 @@ pub struct QueryStat {
 }
 /// Generated by pco_store to store and load compressed versions of [QueryStat]
 pub struct CompressedQueryStats {
-    filter: Option<Filter>,
+    filter: Option<QueryStatFilter>,
     database_id: i64,
     collected_at: Vec<u8>,
     collected_secs: Vec<u8>,
@@ -27,454 +26,96 @@ pub struct CompressedQueryStats {
     shared_blks_hit: Vec<u8>,
     shared_blks_read: Vec<u8>,
 }
-impl CompressedQueryStats {
-    /// Loads data for the specified filters.
-    pub async fn load(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedQueryStats>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        if filter.database_id.is_empty() {
-            return Err(anyhow::Error::msg("database_id".to_string() + " is required"));
-        }
-        if filter.collected_at.is_none() {
-            return Err(anyhow::Error::msg("collected_at".to_string() + " is required"));
-        }
-        filter.range_truncate()?;
-        let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM "
-            + "query_stats" + " WHERE "
-            + "database_id = ANY($1) AND end_at >= $2 AND start_at <= $3";
-        let mut results = Vec::new();
-        for row in db
-            .query(
-                &db.prepare_cached(&sql).await?,
-                &[
-                    &filter.database_id,
-                    filter.collected_at.as_ref().unwrap().start(),
-                    filter.collected_at.as_ref().unwrap().end(),
-                ],
-            )
-            .await?
-        {
-            results.push(fields.load_from_row(row, Some(filter.clone()))?);
-        }
-        Ok(results)
-    }
-    /// Deletes data for the specified filters, returning it to the caller.
-    ///
-    /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
-    pub async fn delete(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedQueryStats>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        if filter.database_id.is_empty() {
-            return Err(anyhow::Error::msg("database_id".to_string() + " is required"));
-        }
-        if filter.collected_at.is_none() {
-            return Err(anyhow::Error::msg("collected_at".to_string() + " is required"));
-        }
-        filter.range_truncate()?;
-        let sql = "DELETE FROM ".to_string() + "query_stats" + " WHERE "
-            + "database_id = ANY($1) AND end_at >= $2 AND start_at <= $3" + " RETURNING "
-            + fields.select().as_str();
-        let mut results = Vec::new();
-        for row in db
-            .query(
-                &db.prepare_cached(&sql).await?,
-                &[
-                    &filter.database_id,
-                    filter.collected_at.as_ref().unwrap().start(),
-                    filter.collected_at.as_ref().unwrap().end(),
-                ],
-            )
-            .await?
-        {
-            results.push(fields.load_from_row(row, None)?);
-        }
-        Ok(results)
-    }
-    /// Decompresses a group of data points.
-    pub fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
-        let mut results = Vec::new();
-        let collected_at: Vec<u64> = if self.collected_at.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.collected_at)?
-        };
-        let collected_secs: Vec<i64> = if self.collected_secs.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.collected_secs)?
-        };
-        let fingerprint: Vec<i64> = if self.fingerprint.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.fingerprint)?
-        };
-        let postgres_role_id: Vec<i64> = if self.postgres_role_id.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.postgres_role_id)?
-        };
-        let calls: Vec<i64> = if self.calls.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.calls)?
-        };
-        let rows: Vec<i64> = if self.rows.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.rows)?
-        };
-        let total_time: Vec<f64> = if self.total_time.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.total_time)?
-        };
-        let io_time: Vec<f64> = if self.io_time.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.io_time)?
-        };
-        let shared_blks_hit: Vec<i64> = if self.shared_blks_hit.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.shared_blks_hit)?
-        };
-        let shared_blks_read: Vec<i64> = if self.shared_blks_read.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.shared_blks_read)?
-        };
-        let len = [
-            collected_at.len(),
-            collected_secs.len(),
-            fingerprint.len(),
-            postgres_role_id.len(),
-            calls.len(),
-            rows.len(),
-            total_time.len(),
-            io_time.len(),
-            shared_blks_hit.len(),
-            shared_blks_read.len(),
-        ]
-            .into_iter()
-            .max()
-            .unwrap_or(0);
-        for index in 0..len {
-            let row = QueryStat {
-                database_id: self.database_id.clone(),
-                collected_at: chrono::DateTime::from_timestamp_micros(
-                        collected_at.get(index).cloned().unwrap_or_default() as i64,
-                    )
-                    .unwrap(),
-                collected_secs: collected_secs.get(index).cloned().unwrap_or_default(),
-                fingerprint: fingerprint.get(index).cloned().unwrap_or_default(),
-                postgres_role_id: postgres_role_id
-                    .get(index)
-                    .cloned()
-                    .unwrap_or_default(),
-                calls: calls.get(index).cloned().unwrap_or_default(),
-                rows: rows.get(index).cloned().unwrap_or_default(),
-                total_time: total_time.get(index).cloned().unwrap_or_default(),
-                io_time: io_time.get(index).cloned().unwrap_or_default(),
-                shared_blks_hit: shared_blks_hit.get(index).cloned().unwrap_or_default(),
-                shared_blks_read: shared_blks_read
-                    .get(index)
-                    .cloned()
-                    .unwrap_or_default(),
-            };
-            if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
-                results.push(row);
-            }
-        }
-        Ok(results)
-    }
-    /// Writes the data to disk.
-    pub async fn store(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<QueryStat>,
-    ) -> anyhow::Result<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows.entry((row.database_id.clone(),)).or_default().push(row);
-        }
-        let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::INT8,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            let collected_at: Vec<_> = rows.iter().map(|s| s.collected_at).collect();
-            let start_at = *collected_at.iter().min().unwrap();
-            let end_at = *collected_at.iter().max().unwrap();
-            let collected_at: Vec<u64> = collected_at
-                .into_iter()
-                .map(|t| t.timestamp_micros() as u64)
-                .collect();
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &rows[0].database_id,
-                        &start_at,
-                        &end_at,
-                        &::pco::standalone::simple_compress(
-                                &collected_at,
-                                &::pco::ChunkConfig::default(),
-                            )
-                            .unwrap(),
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.collected_secs).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.fingerprint).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.postgres_role_id).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.rows).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.io_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.shared_blks_hit).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.shared_blks_read).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-    /// Writes the data to disk, with the provided grouping closure applied.
-    ///
-    /// This can be used to improve the compression ratio and reduce read IO, for example
-    /// by compacting real-time data into a single row per hour / day / week.
-    pub async fn store_grouped<F, R>(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<QueryStat>,
-        grouping: F,
-    ) -> anyhow::Result<()>
-    where
-        F: Fn(&QueryStat) -> R,
-        R: Eq + std::hash::Hash,
-    {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows
-                .entry((row.database_id.clone(), grouping(&row)))
-                .or_default()
-                .push(row);
-        }
-        let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::INT8,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            let collected_at: Vec<_> = rows.iter().map(|s| s.collected_at).collect();
-            let start_at = *collected_at.iter().min().unwrap();
-            let end_at = *collected_at.iter().max().unwrap();
-            let collected_at: Vec<u64> = collected_at
-                .into_iter()
-                .map(|t| t.timestamp_micros() as u64)
-                .collect();
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &rows[0].database_id,
-                        &start_at,
-                        &end_at,
-                        &::pco::standalone::simple_compress(
-                                &collected_at,
-                                &::pco::ChunkConfig::default(),
-                            )
-                            .unwrap(),
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.collected_secs).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.fingerprint).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.postgres_role_id).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.rows).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.io_time).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.shared_blks_hit).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                        &::pco::standalone::simple_compress(
-                            &rows.iter().map(|r| r.shared_blks_read).collect::<Vec<_>>(),
-                            &::pco::ChunkConfig::default(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-}
-#[serde(deny_unknown_fields)]
+#[serde(crate = "::pco_store::__private::serde", deny_unknown_fields)]
 /// Generated by pco_store to specify filters when loading [QueryStat]
-pub struct Filter {
+pub struct QueryStatFilter {
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub database_id: Vec<i64>,
-    #[serde(deserialize_with = "deserialize_time_range")]
+    #[serde(
+        deserialize_with = "::pco_store::__private::deserialize_time_range::<_, _, chrono :: DateTime>"
+    )]
     pub collected_at: Option<std::ops::RangeInclusive<chrono::DateTime>>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub collected_secs: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub fingerprint: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub postgres_role_id: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub calls: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub rows: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub total_time: Vec<f64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub io_time: Vec<f64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub shared_blks_hit: Vec<i64>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub shared_blks_read: Vec<i64>,
 }
 #[automatically_derived]
-impl ::core::fmt::Debug for Filter {
+impl ::core::fmt::Debug for QueryStatFilter {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         let names: &'static _ = &[
@@ -503,14 +144,19 @@ impl ::core::fmt::Debug for Filter {
             &self.shared_blks_hit,
             &&self.shared_blks_read,
         ];
-        ::core::fmt::Formatter::debug_struct_fields_finish(f, "Filter", names, values)
+        ::core::fmt::Formatter::debug_struct_fields_finish(
+            f,
+            "QueryStatFilter",
+            names,
+            values,
+        )
     }
 }
 #[automatically_derived]
-impl ::core::default::Default for Filter {
+impl ::core::default::Default for QueryStatFilter {
     #[inline]
-    fn default() -> Filter {
-        Filter {
+    fn default() -> QueryStatFilter {
+        QueryStatFilter {
             database_id: ::core::default::Default::default(),
             collected_at: ::core::default::Default::default(),
             collected_secs: ::core::default::Default::default(),
@@ -533,10 +179,9 @@ impl ::core::default::Default for Filter {
     clippy::absolute_paths,
 )]
 const _: () = {
-    #[allow(unused_extern_crates, clippy::useless_attribute)]
-    extern crate serde as _serde;
+    use ::pco_store::__private::serde as _serde;
     #[automatically_derived]
-    impl<'de> _serde::Deserialize<'de> for Filter {
+    impl<'de> _serde::Deserialize<'de> for QueryStatFilter {
         fn deserialize<__D>(
             __deserializer: __D,
         ) -> _serde::__private228::Result<Self, __D::Error>
@@ -680,19 +325,19 @@ const _: () = {
             }
             #[doc(hidden)]
             struct __Visitor<'de> {
-                marker: _serde::__private228::PhantomData<Filter>,
+                marker: _serde::__private228::PhantomData<QueryStatFilter>,
                 lifetime: _serde::__private228::PhantomData<&'de ()>,
             }
             #[automatically_derived]
             impl<'de> _serde::de::Visitor<'de> for __Visitor<'de> {
-                type Value = Filter;
+                type Value = QueryStatFilter;
                 fn expecting(
                     &self,
                     __formatter: &mut _serde::__private228::Formatter,
                 ) -> _serde::__private228::fmt::Result {
                     _serde::__private228::Formatter::write_str(
                         __formatter,
-                        "struct Filter",
+                        "struct QueryStatFilter",
                     )
                 }
                 #[inline]
@@ -707,7 +352,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -719,9 +364,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -745,7 +392,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Option<std::ops::RangeInclusive<chrono::DateTime>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -757,7 +404,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: deserialize_time_range(__deserializer)?,
+                                    value: ::pco_store::__private::deserialize_time_range::<
+                                        _,
+                                        _,
+                                        chrono::DateTime,
+                                    >(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
                                     lifetime: _serde::__private228::PhantomData,
                                 })
@@ -775,7 +426,7 @@ const _: () = {
                             return _serde::__private228::Err(
                                 _serde::de::Error::invalid_length(
                                     1usize,
-                                    &"struct Filter with 11 elements",
+                                    &"struct QueryStatFilter with 11 elements",
                                 ),
                             );
                         }
@@ -784,7 +435,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -796,9 +447,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -822,7 +475,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -834,9 +487,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -860,7 +515,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -872,9 +527,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -898,7 +555,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -910,9 +567,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -936,7 +595,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -948,9 +607,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -974,7 +635,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<f64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -986,9 +647,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1012,7 +675,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<f64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1024,9 +687,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1050,7 +715,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1062,9 +727,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1088,7 +755,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<i64>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1100,9 +767,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1122,7 +791,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(QueryStatFilter {
                         database_id: __field0,
                         collected_at: __field1,
                         collected_secs: __field2,
@@ -1173,7 +842,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1186,9 +855,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1218,7 +889,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Option<std::ops::RangeInclusive<chrono::DateTime>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1231,7 +902,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: deserialize_time_range(__deserializer)?,
+                                                value: ::pco_store::__private::deserialize_time_range::<
+                                                    _,
+                                                    _,
+                                                    chrono::DateTime,
+                                                >(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
                                                 lifetime: _serde::__private228::PhantomData,
                                             })
@@ -1259,7 +934,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1272,9 +947,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1304,7 +981,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1317,9 +994,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1349,7 +1028,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1362,9 +1041,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1392,7 +1073,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1405,9 +1086,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1435,7 +1118,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1448,9 +1131,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1480,7 +1165,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<f64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1493,9 +1178,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1525,7 +1212,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<f64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1538,9 +1225,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1570,7 +1259,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1583,9 +1272,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1615,7 +1306,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<i64>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<QueryStatFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1628,9 +1319,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1720,7 +1413,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(QueryStatFilter {
                         database_id: __field0,
                         collected_at: __field1,
                         collected_secs: __field2,
@@ -1751,10 +1444,10 @@ const _: () = {
             ];
             _serde::Deserializer::deserialize_struct(
                 __deserializer,
-                "Filter",
+                "QueryStatFilter",
                 FIELDS,
                 __Visitor {
-                    marker: _serde::__private228::PhantomData::<Filter>,
+                    marker: _serde::__private228::PhantomData::<QueryStatFilter>,
                     lifetime: _serde::__private228::PhantomData,
                 },
             )
@@ -1762,10 +1455,10 @@ const _: () = {
     }
 };
 #[automatically_derived]
-impl ::core::clone::Clone for Filter {
+impl ::core::clone::Clone for QueryStatFilter {
     #[inline]
-    fn clone(&self) -> Filter {
-        Filter {
+    fn clone(&self) -> QueryStatFilter {
+        QueryStatFilter {
             database_id: ::core::clone::Clone::clone(&self.database_id),
             collected_at: ::core::clone::Clone::clone(&self.collected_at),
             collected_secs: ::core::clone::Clone::clone(&self.collected_secs),
@@ -1781,11 +1474,11 @@ impl ::core::clone::Clone for Filter {
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Filter {}
+impl ::core::marker::StructuralPartialEq for QueryStatFilter {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Filter {
+impl ::core::cmp::PartialEq for QueryStatFilter {
     #[inline]
-    fn eq(&self, other: &Filter) -> bool {
+    fn eq(&self, other: &QueryStatFilter) -> bool {
         self.database_id == other.database_id && self.collected_at == other.collected_at
             && self.collected_secs == other.collected_secs
             && self.fingerprint == other.fingerprint
@@ -1796,76 +1489,8 @@ impl ::core::cmp::PartialEq for Filter {
             && self.shared_blks_read == other.shared_blks_read
     }
 }
-impl Filter {
-    /// Builds new filter with the required fields defined by `group_by` and `timestamp`
-    pub fn new(
-        database_id: &[i64],
-        collected_at: std::ops::RangeInclusive<chrono::DateTime>,
-    ) -> Self {
-        Self {
-            database_id: database_id.into(),
-            collected_at: Some(collected_at),
-            ..Self::default()
-        }
-    }
-    fn matches(&self, row: &QueryStat) -> bool {
-        (self.database_id.is_empty() || self.database_id.contains(&row.database_id))
-            && self.collected_at.as_ref().map(|t| t.contains(&row.collected_at))
-                != Some(false)
-            && (self.collected_secs.is_empty()
-                || self.collected_secs.contains(&row.collected_secs))
-            && (self.fingerprint.is_empty()
-                || self.fingerprint.contains(&row.fingerprint))
-            && (self.postgres_role_id.is_empty()
-                || self.postgres_role_id.contains(&row.postgres_role_id))
-            && (self.calls.is_empty() || self.calls.contains(&row.calls))
-            && (self.rows.is_empty() || self.rows.contains(&row.rows))
-            && (self.total_time.is_empty() || self.total_time.contains(&row.total_time))
-            && (self.io_time.is_empty() || self.io_time.contains(&row.io_time))
-            && (self.shared_blks_hit.is_empty()
-                || self.shared_blks_hit.contains(&row.shared_blks_hit))
-            && (self.shared_blks_read.is_empty()
-                || self.shared_blks_read.contains(&row.shared_blks_read))
-    }
-    /// Convenience function to unwrap the timestamp range lower and upper bounds
-    pub fn range_bounds(&self) -> anyhow::Result<(chrono::DateTime, chrono::DateTime)> {
-        use anyhow::Context;
-        let timestamp = self.collected_at.clone().context("no timestamp")?;
-        Ok((*timestamp.start(), *timestamp.end()))
-    }
-    /// Convenience function to return the amount of time the filter covers
-    pub fn range_duration(&self) -> anyhow::Result<chrono::Duration> {
-        let (start, end) = self.range_bounds()?;
-        Ok(end - start)
-    }
-    /// Shifts the filtered time range. This for example makes it easier
-    /// to perform two queries: once for "today", and one for "today, 7 days ago".
-    /// In that example the second query would do `filter.shift(Duration::days(-7))`
-    pub fn range_shift(&mut self, duration: chrono::Duration) -> anyhow::Result<()> {
-        use std::ops::Add;
-        let (start, end) = self.range_bounds()?;
-        self.collected_at = Some(start.add(duration)..=end.add(duration));
-        Ok(())
-    }
-    /// Postgres doesn't support nanosecond precision and nor does MacOS, so this
-    /// truncates nanosecond precision for timestamp comparisons
-    fn range_truncate(&mut self) -> anyhow::Result<()> {
-        let (start, end) = self.range_bounds()?;
-        self.collected_at = Some(
-            Self::truncate_nanos(start)?..=Self::truncate_nanos(end)?,
-        );
-        Ok(())
-    }
-    fn truncate_nanos(time: chrono::DateTime) -> anyhow::Result<chrono::DateTime> {
-        use anyhow::Context;
-        Ok(
-            chrono::DateTime::from_timestamp_micros(time.timestamp_micros())
-                .context("out of range")?,
-        )
-    }
-}
 /// Generated by pco_store to choose which fields to decompress when loading [QueryStat]
-pub struct Fields {
+pub struct QueryStatFields {
     database_id: bool,
     collected_at: bool,
     collected_secs: bool,
@@ -1880,19 +1505,19 @@ pub struct Fields {
 }
 #[automatically_derived]
 #[doc(hidden)]
-unsafe impl ::core::clone::TrivialClone for Fields {}
+unsafe impl ::core::clone::TrivialClone for QueryStatFields {}
 #[automatically_derived]
-impl ::core::clone::Clone for Fields {
+impl ::core::clone::Clone for QueryStatFields {
     #[inline]
-    fn clone(&self) -> Fields {
+    fn clone(&self) -> QueryStatFields {
         let _: ::core::clone::AssertParamIsClone<bool>;
         *self
     }
 }
 #[automatically_derived]
-impl ::core::marker::Copy for Fields {}
+impl ::core::marker::Copy for QueryStatFields {}
 #[automatically_derived]
-impl ::core::fmt::Debug for Fields {
+impl ::core::fmt::Debug for QueryStatFields {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         let names: &'static _ = &[
@@ -1921,15 +1546,20 @@ impl ::core::fmt::Debug for Fields {
             &self.shared_blks_hit,
             &&self.shared_blks_read,
         ];
-        ::core::fmt::Formatter::debug_struct_fields_finish(f, "Fields", names, values)
+        ::core::fmt::Formatter::debug_struct_fields_finish(
+            f,
+            "QueryStatFields",
+            names,
+            values,
+        )
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Fields {}
+impl ::core::marker::StructuralPartialEq for QueryStatFields {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Fields {
+impl ::core::cmp::PartialEq for QueryStatFields {
     #[inline]
-    fn eq(&self, other: &Fields) -> bool {
+    fn eq(&self, other: &QueryStatFields) -> bool {
         self.database_id == other.database_id && self.collected_at == other.collected_at
             && self.collected_secs == other.collected_secs
             && self.fingerprint == other.fingerprint
@@ -1940,383 +1570,1117 @@ impl ::core::cmp::PartialEq for Fields {
             && self.shared_blks_read == other.shared_blks_read
     }
 }
-impl Fields {
-    pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
-        fields.try_into().map_err(|e| anyhow::Error::msg(e))
-    }
-    pub fn required() -> Self {
-        Self {
-            database_id: true,
-            collected_at: true,
-            collected_secs: false,
-            fingerprint: false,
-            postgres_role_id: false,
-            calls: false,
-            rows: false,
-            total_time: false,
-            io_time: false,
-            shared_blks_hit: false,
-            shared_blks_read: false,
+const _: () = {
+    #[allow(unused_imports)]
+    use ::pco_store::__private::{
+        ahash, anyhow, deadpool_postgres, futures, pco, rmp_serde, serde, serde_bytes,
+        serde_with, tokio, tokio_postgres, zstd,
+    };
+    use ::pco_store::__private::*;
+    type Filter = QueryStatFilter;
+    type Fields = QueryStatFields;
+    impl CompressedQueryStats {
+        /// Loads data for the specified filters.
+        pub async fn load(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedQueryStats>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            if filter.database_id.is_empty() {
+                return Err(
+                    anyhow::Error::msg("database_id".to_string() + " is required"),
+                );
+            }
+            if filter.collected_at.is_none() {
+                return Err(
+                    anyhow::Error::msg("collected_at".to_string() + " is required"),
+                );
+            }
+            filter.range_truncate()?;
+            let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM "
+                + "query_stats" + " WHERE "
+                + "database_id = ANY($1) AND end_at >= $2 AND start_at <= $3";
+            let mut results = Vec::new();
+            for row in db
+                .query(
+                    &db.prepare_cached(&sql).await?,
+                    &[
+                        &filter.database_id,
+                        &std::time::SystemTime::from(
+                            (*filter.collected_at.as_ref().unwrap().start()),
+                        ),
+                        &std::time::SystemTime::from(
+                            (*filter.collected_at.as_ref().unwrap().end()),
+                        ),
+                    ],
+                )
+                .await?
+            {
+                results.push(fields.load_from_row(row, Some(filter.clone()))?);
+            }
+            Ok(results)
         }
-    }
-    fn merge_filter(&mut self, filter: &Filter) {
-        (!filter.collected_secs.is_empty()).then(|| self.collected_secs = true);
-        (!filter.fingerprint.is_empty()).then(|| self.fingerprint = true);
-        (!filter.postgres_role_id.is_empty()).then(|| self.postgres_role_id = true);
-        (!filter.calls.is_empty()).then(|| self.calls = true);
-        (!filter.rows.is_empty()).then(|| self.rows = true);
-        (!filter.total_time.is_empty()).then(|| self.total_time = true);
-        (!filter.io_time.is_empty()).then(|| self.io_time = true);
-        (!filter.shared_blks_hit.is_empty()).then(|| self.shared_blks_hit = true);
-        (!filter.shared_blks_read.is_empty()).then(|| self.shared_blks_read = true);
-    }
-    fn select(&self) -> String {
-        let mut fields = Vec::new();
-        self.database_id.then(|| fields.push("database_id"));
-        self.collected_at.then(|| fields.push("collected_at"));
-        self.collected_secs.then(|| fields.push("collected_secs"));
-        self.fingerprint.then(|| fields.push("fingerprint"));
-        self.postgres_role_id.then(|| fields.push("postgres_role_id"));
-        self.calls.then(|| fields.push("calls"));
-        self.rows.then(|| fields.push("rows"));
-        self.total_time.then(|| fields.push("total_time"));
-        self.io_time.then(|| fields.push("io_time"));
-        self.shared_blks_hit.then(|| fields.push("shared_blks_hit"));
-        self.shared_blks_read.then(|| fields.push("shared_blks_read"));
-        fields.join(", ")
-    }
-    fn load_from_row(
-        &self,
-        row: tokio_postgres::Row,
-        filter: Option<Filter>,
-    ) -> anyhow::Result<CompressedQueryStats> {
-        let mut index = 0;
-        Ok(CompressedQueryStats {
-            filter,
-            database_id: if self.database_id {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            collected_at: if self.collected_at {
-                let v = row.get(index);
-                index += 1;
-                v
+        /// Deletes data for the specified filters, returning it to the caller.
+        ///
+        /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
+        pub async fn delete(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedQueryStats>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            if filter.database_id.is_empty() {
+                return Err(
+                    anyhow::Error::msg("database_id".to_string() + " is required"),
+                );
+            }
+            if filter.collected_at.is_none() {
+                return Err(
+                    anyhow::Error::msg("collected_at".to_string() + " is required"),
+                );
+            }
+            filter.range_truncate()?;
+            let sql = "DELETE FROM ".to_string() + "query_stats" + " WHERE "
+                + "database_id = ANY($1) AND end_at >= $2 AND start_at <= $3"
+                + " RETURNING " + fields.select().as_str();
+            let mut results = Vec::new();
+            for row in db
+                .query(
+                    &db.prepare_cached(&sql).await?,
+                    &[
+                        &filter.database_id,
+                        &std::time::SystemTime::from(
+                            (*filter.collected_at.as_ref().unwrap().start()),
+                        ),
+                        &std::time::SystemTime::from(
+                            (*filter.collected_at.as_ref().unwrap().end()),
+                        ),
+                    ],
+                )
+                .await?
+            {
+                results.push(fields.load_from_row(row, None)?);
+            }
+            Ok(results)
+        }
+        /// Decompresses a group of data points.
+        pub fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
+            let mut results = Vec::new();
+            let collected_at: Vec<i64> = pco_decompress_timestamps(
+                &self.collected_at,
+                1000000i64,
+            )?;
+            let collected_secs: Vec<i64> = if self.collected_secs.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            collected_secs: if self.collected_secs {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.collected_secs)?
+            };
+            let fingerprint: Vec<i64> = if self.fingerprint.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            fingerprint: if self.fingerprint {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.fingerprint)?
+            };
+            let postgres_role_id: Vec<i64> = if self.postgres_role_id.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            postgres_role_id: if self.postgres_role_id {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.postgres_role_id)?
+            };
+            let calls: Vec<i64> = if self.calls.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            calls: if self.calls {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.calls)?
+            };
+            let rows: Vec<i64> = if self.rows.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            rows: if self.rows {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.rows)?
+            };
+            let total_time: Vec<f64> = if self.total_time.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            total_time: if self.total_time {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.total_time)?
+            };
+            let io_time: Vec<f64> = if self.io_time.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            io_time: if self.io_time {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.io_time)?
+            };
+            let shared_blks_hit: Vec<i64> = if self.shared_blks_hit.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            shared_blks_hit: if self.shared_blks_hit {
-                let v = row.get(index);
-                index += 1;
-                v
+                pco::standalone::simple_decompress(&self.shared_blks_hit)?
+            };
+            let shared_blks_read: Vec<i64> = if self.shared_blks_read.is_empty() {
+                Vec::new()
             } else {
-                Default::default()
-            },
-            shared_blks_read: if self.shared_blks_read {
-                let v = row.get(index);
+                pco::standalone::simple_decompress(&self.shared_blks_read)?
+            };
+            let len = [
+                Some(collected_at.len()),
+                Some(collected_secs.len()),
+                Some(fingerprint.len()),
+                Some(postgres_role_id.len()),
+                Some(calls.len()),
+                Some(rows.len()),
+                Some(total_time.len()),
+                Some(io_time.len()),
+                Some(shared_blks_hit.len()),
+                Some(shared_blks_read.len()),
+            ]
+                .into_iter()
+                .flatten()
+                .max()
+                .filter(|len| *len > 0);
+            let mut index = 0;
+            while len.map_or_else(|| [].contains(&true), |len| index < len) {
+                let row = QueryStat {
+                    database_id: self.database_id.clone(),
+                    collected_at: chrono::DateTime::from_timestamp_micros(
+                            collected_at.get(index).cloned().unwrap_or_default(),
+                        )
+                        .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    collected_secs: collected_secs
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default(),
+                    fingerprint: fingerprint.get(index).cloned().unwrap_or_default(),
+                    postgres_role_id: postgres_role_id
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default(),
+                    calls: calls.get(index).cloned().unwrap_or_default(),
+                    rows: rows.get(index).cloned().unwrap_or_default(),
+                    total_time: total_time.get(index).cloned().unwrap_or_default(),
+                    io_time: io_time.get(index).cloned().unwrap_or_default(),
+                    shared_blks_hit: shared_blks_hit
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default(),
+                    shared_blks_read: shared_blks_read
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default(),
+                };
+                if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
+                    results.push(row);
+                }
                 index += 1;
-                v
-            } else {
-                Default::default()
-            },
-        })
-    }
-}
-impl Default for Fields {
-    fn default() -> Self {
-        Self {
-            database_id: true,
-            collected_at: true,
-            collected_secs: true,
-            fingerprint: true,
-            postgres_role_id: true,
-            calls: true,
-            rows: true,
-            total_time: true,
-            io_time: true,
-            shared_blks_hit: true,
-            shared_blks_read: true,
+            }
+            Ok(results)
         }
-    }
-}
-impl TryFrom<&[&str]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
-        let mut fields = Self::required();
-        for s in input {
-            match *s {
-                "database_id" => fields.database_id = true,
-                "collected_at" => fields.collected_at = true,
-                "collected_secs" => fields.collected_secs = true,
-                "fingerprint" => fields.fingerprint = true,
-                "postgres_role_id" => fields.postgres_role_id = true,
-                "calls" => fields.calls = true,
-                "rows" => fields.rows = true,
-                "total_time" => fields.total_time = true,
-                "io_time" => fields.io_time = true,
-                "shared_blks_hit" => fields.shared_blks_hit = true,
-                "shared_blks_read" => fields.shared_blks_read = true,
-                _ => return Err("unknown field"),
+        /// Decompresses many groups in parallel, returning their data points in the same order as the groups.
+        pub async fn decompress_all(
+            groups: Vec<Self>,
+        ) -> anyhow::Result<Vec<QueryStat>> {
+            if groups.is_empty() {
+                return Ok(Vec::new());
             }
+            let threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let chunk_size = groups.len().div_ceil(threads);
+            let mut groups = groups.into_iter();
+            let mut tasks = Vec::new();
+            loop {
+                let chunk: Vec<Self> = groups.by_ref().take(chunk_size).collect();
+                if chunk.is_empty() {
+                    break;
+                }
+                tasks
+                    .push(
+                        tokio::task::spawn_blocking(move || {
+                            chunk
+                                .into_iter()
+                                .map(Self::decompress)
+                                .collect::<anyhow::Result<Vec<_>>>()
+                        }),
+                    );
+            }
+            let mut groups: Vec<Vec<QueryStat>> = Vec::new();
+            for task in tasks {
+                groups.extend(task.await??);
+            }
+            Ok(groups.into_iter().flatten().collect())
+        }
+        /// Decompresses many groups in parallel like [decompress_all][Self::decompress_all], returning their data points sorted by `collected_at`.
+        ///
+        /// Data points with the same timestamp keep the order of their groups.
+        pub async fn decompress_all_sorted(
+            groups: Vec<Self>,
+        ) -> anyhow::Result<Vec<QueryStat>> {
+            let mut rows = Self::decompress_all(groups).await?;
+            let rows = tokio::task::spawn_blocking(move || {
+                rows.sort_by(|a, b| a.collected_at.cmp(&b.collected_at));
+                rows
+            });
+            Ok(rows.await?)
+        }
+        /// Compresses the fields of a group, returning the rows along with their timestamp range.
+        fn compress_group(
+            rows: Vec<QueryStat>,
+        ) -> anyhow::Result<(Vec<QueryStat>, (i64, i64), Vec<Vec<u8>>)> {
+            let mut collected_at: Vec<i64> = Vec::with_capacity(rows.len());
+            for s in rows.iter() {
+                collected_at.push(s.collected_at.timestamp_micros());
+            }
+            let bounds = (
+                *collected_at.iter().min().unwrap(),
+                *collected_at.iter().max().unwrap(),
+            );
+            let compressed: Vec<Vec<u8>> = <[_]>::into_vec(
+                ::alloc::boxed::box_new([
+                    pco::standalone::simple_compress(
+                        &collected_at,
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.collected_secs).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.fingerprint).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.postgres_role_id).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.calls).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.rows).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.total_time).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.io_time).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.shared_blks_hit).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    pco::standalone::simple_compress(
+                        &rows.iter().map(|r| r.shared_blks_read).collect::<Vec<_>>(),
+                        &pco::ChunkConfig::default(),
+                    )?,
+                ]),
+            );
+            Ok((rows, bounds, compressed))
+        }
+        /// Compresses each part of the data that will be written as a row.
+        async fn compress_parts(
+            parts: Vec<Vec<QueryStat>>,
+        ) -> anyhow::Result<Vec<(Vec<QueryStat>, (i64, i64), Vec<Vec<u8>>)>> {
+            parts.into_iter().map(Self::compress_group).collect()
+        }
+        /// Writes the data to disk.
+        pub async fn store(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = QueryStat>,
+        ) -> anyhow::Result<()> {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows.entry((row.database_id.clone(),)).or_default().push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::INT8,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].database_id,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                                &compressed[7usize],
+                                &compressed[8usize],
+                                &compressed[9usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk, with the provided grouping closure applied.
+        ///
+        /// This can be used to improve the compression ratio and reduce read IO, for example
+        /// by compacting real-time data into a single row per hour / day / week.
+        pub async fn store_grouped<F, R>(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = QueryStat>,
+            grouping: F,
+        ) -> anyhow::Result<()>
+        where
+            F: Fn(&QueryStat) -> R,
+            R: Eq + std::hash::Hash,
+        {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows
+                    .entry((row.database_id.clone(), grouping(&row)))
+                    .or_default()
+                    .push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::INT8,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].database_id,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                                &compressed[7usize],
+                                &compressed[8usize],
+                                &compressed[9usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk as it's received from a stream, for bulk imports that don't fit in memory.
+        ///
+        /// Rows are grouped the same way as [store][Self::store], and each group is written once it has `group_size`
+        /// rows. Any remaining groups are written when the stream ends. Duplicates are only removed within a group.
+        pub async fn store_stream(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl futures::Stream<Item = QueryStat>,
+            group_size: usize,
+        ) -> anyhow::Result<()> {
+            if group_size == 0 {
+                return Err(anyhow::Error::msg("group_size must be greater than zero"));
+            }
+            let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::INT8,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let mut rows = rows;
+            #[allow(unused_mut)]
+            let mut rows = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut rows)
+            };
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            while let Some(row) = futures::StreamExt::next(&mut rows).await {
+                let group = grouped_rows.entry((row.database_id.clone(),)).or_default();
+                group.push(row);
+                if group.len() >= group_size {
+                    let rows = std::mem::take(group);
+                    let groups = std::iter::once(rows);
+                    let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+                    while !parts.is_empty() {
+                        for (rows, bounds, compressed) in Self::compress_parts(
+                                std::mem::take(&mut parts),
+                            )
+                            .await?
+                        {
+                            let start_at = std::time::SystemTime::from(
+                                chrono::DateTime::from_timestamp_micros(bounds.0)
+                                    .ok_or_else(|| anyhow::Error::msg(
+                                        "timestamp out of range",
+                                    ))?,
+                            );
+                            let end_at = std::time::SystemTime::from(
+                                chrono::DateTime::from_timestamp_micros(bounds.1)
+                                    .ok_or_else(|| anyhow::Error::msg(
+                                        "timestamp out of range",
+                                    ))?,
+                            );
+                            writer
+                                .as_mut()
+                                .write(
+                                    &[
+                                        &rows[0].database_id,
+                                        &start_at,
+                                        &end_at,
+                                        &compressed[0usize],
+                                        &compressed[1usize],
+                                        &compressed[2usize],
+                                        &compressed[3usize],
+                                        &compressed[4usize],
+                                        &compressed[5usize],
+                                        &compressed[6usize],
+                                        &compressed[7usize],
+                                        &compressed[8usize],
+                                        &compressed[9usize],
+                                    ],
+                                )
+                                .await?;
+                        }
+                    }
+                }
+            }
+            let groups = grouped_rows
+                .into_values()
+                .filter(|rows| !rows.is_empty())
+                .map(|rows| { rows });
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].database_id,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                                &compressed[7usize],
+                                &compressed[8usize],
+                                &compressed[9usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Merges the data into the existing groups for each `group_by` value and `bucket` of time, in a single transaction.
+        ///
+        /// Existing groups that fall entirely within a bucket are locked with `SELECT ... FOR UPDATE`, decompressed,
+        /// combined with the new rows and written back as one group. This keeps low-volume data compacted without
+        /// a separate rewrite job. This runs its own transaction, so it can't be called inside of another one. The
+        /// transaction is rolled back if it fails, or if the returned future is dropped before it completes.
+        pub async fn store_merge(
+            db: &mut deadpool_postgres::ClientWrapper,
+            rows: Vec<QueryStat>,
+            bucket: std::time::Duration,
+        ) -> anyhow::Result<()> {
+            if rows.is_empty() {
+                return Ok(());
+            }
+            let units = i64::try_from(bucket.as_nanos() as i128 / 1000i128)
+                .unwrap_or(i64::MAX);
+            if units == 0 {
+                return Err(
+                    anyhow::Error::msg(
+                        "bucket must be at least one timestamp_precision unit",
+                    ),
+                );
+            }
+            let mut grouped_rows: ahash::AHashMap<_, Vec<QueryStat>> = ahash::AHashMap::new();
+            for row in rows {
+                let bucket = (row.collected_at.timestamp_micros()).div_euclid(units);
+                grouped_rows
+                    .entry((row.database_id.clone(), bucket))
+                    .or_default()
+                    .push(row);
+            }
+            let fields = Fields::default();
+            let select_sql = ::alloc::__export::must_use({
+                ::alloc::fmt::format(
+                    format_args!(
+                        "SELECT {0}, ctid::text FROM query_stats WHERE database_id = $1 AND start_at >= $2 AND end_at <= $3 FOR UPDATE",
+                        fields.select(),
+                    ),
+                )
+            });
+            let transaction = db.transaction().await?;
+            let mut merged_groups = Vec::new();
+            for ((.., bucket), rows) in grouped_rows {
+                let start = system_time_from_int(bucket * units, 1000i128)?;
+                let end = system_time_from_int(bucket * units + (units - 1), 1000i128)?;
+                let mut merged = Vec::new();
+                let mut ctids = Vec::new();
+                for row in transaction
+                    .query(
+                        &transaction.prepare_cached(&select_sql).await?,
+                        &[&rows[0].database_id, &start, &end],
+                    )
+                    .await?
+                {
+                    ctids.push(row.get::<_, String>(row.len() - 1));
+                    merged.extend(fields.load_from_row(row, None)?.decompress()?);
+                }
+                if !ctids.is_empty() {
+                    transaction
+                        .execute(
+                            &transaction
+                                .prepare_cached(
+                                    "DELETE FROM query_stats WHERE database_id = $1 AND ctid = ANY($2::text[]::tid[])",
+                                )
+                                .await?,
+                            &[&rows[0].database_id, &ctids],
+                        )
+                        .await?;
+                }
+                merged.extend(rows);
+                merged_groups.push(merged);
+            }
+            let sql = "COPY query_stats (database_id, start_at, end_at, collected_at, collected_secs, fingerprint, postgres_role_id, calls, rows, total_time, io_time, shared_blks_hit, shared_blks_read) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::INT8,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = transaction
+                .copy_in(&transaction.prepare_cached(&sql).await?)
+                .await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = merged_groups.into_iter().map(|rows| { rows });
+            let mut parts: Vec<Vec<QueryStat>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].database_id,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                                &compressed[7usize],
+                                &compressed[8usize],
+                                &compressed[9usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            transaction.commit().await?;
+            Ok(())
         }
-        Ok(fields)
-    }
-}
-impl<const N: usize> TryFrom<&[&str; N]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
-        Self::try_from(&input[..])
-    }
-}
-impl TryFrom<Vec<String>> for Fields {
-    type Error = &'static str;
-    fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
-        let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
-        Self::try_from(input.as_slice())
     }
-}
-impl From<()> for Fields {
-    fn from(_: ()) -> Self {
-        Self::default()
+    impl ::pco_store::PcoStore for CompressedQueryStats {
+        type Row = QueryStat;
+        type Filter = Filter;
+        type Fields = Fields;
+        const TABLE_NAME: &'static str = "query_stats";
+        const COLUMNS: &'static [&'static str] = &[
+            "database_id",
+            "start_at",
+            "end_at",
+            "collected_at",
+            "collected_secs",
+            "fingerprint",
+            "postgres_role_id",
+            "calls",
+            "rows",
+            "total_time",
+            "io_time",
+            "shared_blks_hit",
+            "shared_blks_read",
+        ];
+        const GROUP_BY: &'static [&'static str] = &["database_id"];
+        const TIMESTAMP: Option<&'static str> = Some("collected_at");
+        fn store<D>(
+            db: &D,
+            rows: impl IntoIterator<Item = QueryStat> + Send,
+        ) -> impl std::future::Future<Output = anyhow::Result<()>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::store(db, rows)
+        }
+        fn load<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::load(db, filter, fields)
+        }
+        fn delete<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::delete(db, filter, fields)
+        }
+        fn decompress(self) -> anyhow::Result<Vec<QueryStat>> {
+            Self::decompress(self)
+        }
+        fn decompress_all(
+            groups: Vec<Self>,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<QueryStat>>> + Send {
+            Self::decompress_all(groups)
+        }
     }
-}
-impl<'de> serde::Deserialize<'de> for Fields {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_any(FieldsVisitor)
+    impl Filter {
+        /// Builds new filter with the required fields defined by `group_by` and `timestamp`
+        pub fn new(
+            database_id: &[i64],
+            collected_at: std::ops::RangeInclusive<chrono::DateTime>,
+        ) -> Self {
+            Self {
+                database_id: database_id.into(),
+                collected_at: Some(collected_at),
+                ..Self::default()
+            }
+        }
+        fn matches(&self, row: &QueryStat) -> bool {
+            (self.database_id.is_empty() || self.database_id.contains(&row.database_id))
+                && self.collected_at.as_ref().map(|t| t.contains(&row.collected_at))
+                    != Some(false)
+                && (self.collected_secs.is_empty()
+                    || self.collected_secs.contains(&row.collected_secs))
+                && (self.fingerprint.is_empty()
+                    || self.fingerprint.contains(&row.fingerprint))
+                && (self.postgres_role_id.is_empty()
+                    || self.postgres_role_id.contains(&row.postgres_role_id))
+                && (self.calls.is_empty() || self.calls.contains(&row.calls))
+                && (self.rows.is_empty() || self.rows.contains(&row.rows))
+                && (self.total_time.is_empty()
+                    || self.total_time.contains(&row.total_time))
+                && (self.io_time.is_empty() || self.io_time.contains(&row.io_time))
+                && (self.shared_blks_hit.is_empty()
+                    || self.shared_blks_hit.contains(&row.shared_blks_hit))
+                && (self.shared_blks_read.is_empty()
+                    || self.shared_blks_read.contains(&row.shared_blks_read))
+        }
+        /// Convenience function to unwrap the timestamp range lower and upper bounds
+        pub fn range_bounds(
+            &self,
+        ) -> anyhow::Result<(chrono::DateTime, chrono::DateTime)> {
+            use anyhow::Context;
+            let timestamp = self.collected_at.clone().context("no timestamp")?;
+            Ok((*timestamp.start(), *timestamp.end()))
+        }
+        /// Convenience function to return the amount of time the filter covers
+        pub fn range_duration(&self) -> anyhow::Result<chrono::Duration> {
+            let (start, end) = self.range_bounds()?;
+            Ok(end - start)
+        }
+        /// Shifts the filtered time range. This for example makes it easier
+        /// to perform two queries: once for "today", and one for "today, 7 days ago".
+        /// In that example the second query would do `filter.shift(Duration::days(-7))`
+        pub fn range_shift(&mut self, duration: chrono::Duration) -> anyhow::Result<()> {
+            use std::ops::Add;
+            let (start, end) = self.range_bounds()?;
+            self.collected_at = Some(start.add(duration)..=end.add(duration));
+            Ok(())
+        }
+        /// Timestamps are stored with the `timestamp_precision` given to the macro (microseconds by default),
+        /// so this truncates the time range to that precision for timestamp comparisons
+        fn range_truncate(&mut self) -> anyhow::Result<()> {
+            let (start, end) = self.range_bounds()?;
+            self.collected_at = Some(Self::truncate(start)?..=Self::truncate(end)?);
+            Ok(())
+        }
+        fn truncate(time: chrono::DateTime) -> anyhow::Result<chrono::DateTime> {
+            let value = time.timestamp_micros();
+            let time = chrono::DateTime::from_timestamp_micros(value)
+                .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?;
+            Ok(time)
+        }
     }
-}
-struct FieldsVisitor;
-impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
-    type Value = Fields;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("an array of strings matching the struct fields")
+    impl Fields {
+        pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
+            fields.try_into().map_err(|e| anyhow::Error::msg(e))
+        }
+        pub fn required() -> Self {
+            Self {
+                database_id: true,
+                collected_at: true,
+                collected_secs: false,
+                fingerprint: false,
+                postgres_role_id: false,
+                calls: false,
+                rows: false,
+                total_time: false,
+                io_time: false,
+                shared_blks_hit: false,
+                shared_blks_read: false,
+            }
+        }
+        fn merge_filter(&mut self, filter: &Filter) {
+            (!filter.collected_secs.is_empty()).then(|| self.collected_secs = true);
+            (!filter.fingerprint.is_empty()).then(|| self.fingerprint = true);
+            (!filter.postgres_role_id.is_empty()).then(|| self.postgres_role_id = true);
+            (!filter.calls.is_empty()).then(|| self.calls = true);
+            (!filter.rows.is_empty()).then(|| self.rows = true);
+            (!filter.total_time.is_empty()).then(|| self.total_time = true);
+            (!filter.io_time.is_empty()).then(|| self.io_time = true);
+            (!filter.shared_blks_hit.is_empty()).then(|| self.shared_blks_hit = true);
+            (!filter.shared_blks_read.is_empty()).then(|| self.shared_blks_read = true);
+        }
+        fn select(&self) -> String {
+            let mut fields = Vec::new();
+            self.database_id.then(|| fields.push("database_id"));
+            self.collected_at.then(|| fields.push("collected_at"));
+            self.collected_secs.then(|| fields.push("collected_secs"));
+            self.fingerprint.then(|| fields.push("fingerprint"));
+            self.postgres_role_id.then(|| fields.push("postgres_role_id"));
+            self.calls.then(|| fields.push("calls"));
+            self.rows.then(|| fields.push("rows"));
+            self.total_time.then(|| fields.push("total_time"));
+            self.io_time.then(|| fields.push("io_time"));
+            self.shared_blks_hit.then(|| fields.push("shared_blks_hit"));
+            self.shared_blks_read.then(|| fields.push("shared_blks_read"));
+            fields.join(", ")
+        }
+        fn load_from_row(
+            &self,
+            row: tokio_postgres::Row,
+            filter: Option<Filter>,
+        ) -> anyhow::Result<CompressedQueryStats> {
+            let mut index = 0;
+            Ok(CompressedQueryStats {
+                filter,
+                database_id: {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                },
+                collected_at: if self.collected_at {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                collected_secs: if self.collected_secs {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                fingerprint: if self.fingerprint {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                postgres_role_id: if self.postgres_role_id {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                calls: if self.calls {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                rows: if self.rows {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                total_time: if self.total_time {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                io_time: if self.io_time {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                shared_blks_hit: if self.shared_blks_hit {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                shared_blks_read: if self.shared_blks_read {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+            })
+        }
     }
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let mut fields = Vec::new();
-        while let Some(field) = seq.next_element()? {
-            fields.push(field);
+    impl Default for Fields {
+        fn default() -> Self {
+            Self {
+                database_id: true,
+                collected_at: true,
+                collected_secs: true,
+                fingerprint: true,
+                postgres_role_id: true,
+                calls: true,
+                rows: true,
+                total_time: true,
+                io_time: true,
+                shared_blks_hit: true,
+                shared_blks_read: true,
+            }
         }
-        Fields::try_from(fields).map_err(serde::de::Error::custom)
     }
-    fn visit_unit<E>(self) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(Fields::default())
+    impl TryFrom<&[&str]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
+            let mut fields = Self::required();
+            for s in input {
+                match *s {
+                    "database_id" => fields.database_id = true,
+                    "collected_at" => fields.collected_at = true,
+                    "collected_secs" => fields.collected_secs = true,
+                    "fingerprint" => fields.fingerprint = true,
+                    "postgres_role_id" => fields.postgres_role_id = true,
+                    "calls" => fields.calls = true,
+                    "rows" => fields.rows = true,
+                    "total_time" => fields.total_time = true,
+                    "io_time" => fields.io_time = true,
+                    "shared_blks_hit" => fields.shared_blks_hit = true,
+                    "shared_blks_read" => fields.shared_blks_read = true,
+                    _ => return Err("unknown field"),
+                }
+            }
+            Ok(fields)
+        }
     }
-}
-/// Deserializes many different time range formats:
-/// - an array with two strings becomes a normal time range: ["a", "b"] -> a..=b
-/// - an array with one string becomes a single-value time range: ["a"] -> a..=a
-/// - a string literal becomes a single-value time range:           "a" -> a..=a
-fn deserialize_time_range<'de, D>(
-    deserializer: D,
-) -> Result<Option<std::ops::RangeInclusive<chrono::DateTime>>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    Ok(TimeRange::deserialize(deserializer)?.0)
-}
-struct TimeRange(Option<std::ops::RangeInclusive<chrono::DateTime>>);
-#[automatically_derived]
-impl ::core::fmt::Debug for TimeRange {
-    #[inline]
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        ::core::fmt::Formatter::debug_tuple_field1_finish(f, "TimeRange", &&self.0)
+    impl<const N: usize> TryFrom<&[&str; N]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
+            Self::try_from(&input[..])
+        }
     }
-}
-#[automatically_derived]
-impl ::core::marker::StructuralPartialEq for TimeRange {}
-#[automatically_derived]
-impl ::core::cmp::PartialEq for TimeRange {
-    #[inline]
-    fn eq(&self, other: &TimeRange) -> bool {
-        self.0 == other.0
+    impl TryFrom<Vec<String>> for Fields {
+        type Error = &'static str;
+        fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
+            let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
+            Self::try_from(input.as_slice())
+        }
     }
-}
-impl<'de> serde::Deserialize<'de> for TimeRange {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_any(TimeRangeVisitor)
+    impl From<()> for Fields {
+        fn from(_: ()) -> Self {
+            Self::default()
+        }
     }
-}
-struct TimeRangeVisitor;
-impl<'de> serde::de::Visitor<'de> for TimeRangeVisitor {
-    type Value = TimeRange;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a single time string or an array with 1-2 time strings")
+    impl<'de> serde::Deserialize<'de> for Fields {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(FieldsVisitor)
+        }
     }
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        if value.is_empty() {
-            return Ok(TimeRange(None));
+    struct FieldsVisitor;
+    impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+        type Value = Fields;
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an array of strings matching the struct fields")
         }
-        match serde::Deserialize::deserialize(
-            serde::de::value::StrDeserializer::<E>::new(value),
-        ) {
-            Ok(start) => Ok(TimeRange(Some(start..=start))),
-            Err(err) => {
-                Err(
-                    E::custom(
-                        "invalid time format: ".to_string() + err.to_string().as_str(),
-                    ),
-                )
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut fields = Vec::new();
+            while let Some(field) = seq.next_element()? {
+                fields.push(field);
             }
+            Fields::try_from(fields).map_err(serde::de::Error::custom)
         }
-    }
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let start = match seq.next_element::<Option<chrono::DateTime>>()? {
-            Some(Some(time)) => time,
-            Some(None) | None => return Ok(TimeRange(None)),
-        };
-        let end = match seq.next_element::<Option<chrono::DateTime>>()? {
-            Some(Some(time)) => time,
-            Some(None) | None => start,
-        };
-        Ok(TimeRange(Some(start..=end)))
-    }
-    fn visit_unit<E>(self) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(TimeRange(None))
-    }
-}
-fn serde_compress<T>(items: Vec<T>) -> anyhow::Result<Vec<u8>>
-where
-    T: serde::Serialize,
-{
-    use std::io::Write;
-    let mut output = Vec::new();
-    let mut encoder = zstd::stream::write::Encoder::new(&mut output, 3)?;
-    for item in items {
-        rmp_serde::encode::write(&mut encoder, &item)?;
-    }
-    encoder.finish()?;
-    Ok(output)
-}
-fn serde_decompress<T>(input: &[u8]) -> impl Iterator<Item = anyhow::Result<T>> + '_
-where
-    T: for<'de> serde::Deserialize<'de> + 'static,
-{
-    let decoder = match zstd::stream::read::Decoder::new(input) {
-        Ok(d) => d,
-        Err(e) => {
-            return Box::new(std::iter::once(Err(e.into())))
-                as Box<dyn Iterator<Item = _>>;
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(Fields::default())
         }
-    };
-    let buffered = std::io::BufReader::with_capacity(128 * 1024, decoder);
-    let mut de = rmp_serde::decode::Deserializer::new(buffered);
-    Box::new(
-        std::iter::from_fn(move || match serde::Deserialize::deserialize(&mut de) {
-            Ok(item) => Some(Ok(item)),
-            Err(
-                rmp_serde::decode::Error::InvalidMarkerRead(ref e),
-            ) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
-            Err(e) => Some(Err(e.into())),
-        }),
-    )
-}
-fn pco_compress_nested<T>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>>
-where
-    T: ::pco::data_types::Number,
-{
-    let mut lengths = Vec::new();
-    let mut values = Vec::new();
-    for vals in nested_values {
-        lengths.push(vals.len() as u64);
-        values.extend(vals);
     }
-    let length_bytes = ::pco::standalone::simple_compress(
-        &lengths,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let value_bytes = ::pco::standalone::simple_compress(
-        &values,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let (length_bytes, value_bytes) = (
-        serde_bytes::Bytes::new(&length_bytes),
-        serde_bytes::Bytes::new(&value_bytes),
-    );
-    Ok(rmp_serde::to_vec(&(length_bytes, value_bytes))?)
-}
-fn pco_decompress_nested<T>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>>
-where
-    T: ::pco::data_types::Number,
-{
-    let (length_bytes, value_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(&bytes)?;
-    let lengths = ::pco::standalone::simple_decompress::<u64>(&length_bytes)?;
-    let values = ::pco::standalone::simple_decompress::<T>(&value_bytes)?;
-    let mut values = values.into_iter();
-    let mut nested_values = Vec::with_capacity(lengths.len());
-    for length in lengths {
-        nested_values.push(values.by_ref().take(length as usize).collect::<Vec<T>>());
-    }
-    Ok(nested_values)
-}
+};
@@ -1,4 +1,3 @@
-use serde::Deserialize as _;
 pub struct Serde {
     pub id: Uuid,
     pub name: String,
@@ -659,7 +658,7 @@ const _: () = {
 };
 /// Generated by pco_store to store and load compressed versions of [Serde]
 pub struct CompressedSerdes {
-    filter: Option<Filter>,
+    filter: Option<SerdeFilter>,
     id: Uuid,
     name: String,
     time: Vec<u8>,
@@ -670,367 +669,80 @@ pub struct CompressedSerdes {
     json: Vec<u8>,
     model: Vec<u8>,
 }
-impl CompressedSerdes {
-    /// Loads data for the specified filters.
-    pub async fn load(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedSerdes>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        if filter.id.is_empty() {
-            return Err(anyhow::Error::msg("id".to_string() + " is required"));
-        }
-        if filter.name.is_empty() {
-            return Err(anyhow::Error::msg("name".to_string() + " is required"));
-        }
-        if filter.time.is_none() {
-            return Err(anyhow::Error::msg("time".to_string() + " is required"));
-        }
-        filter.range_truncate()?;
-        let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM " + "serdes"
-            + " WHERE "
-            + "id = ANY($1) AND name = ANY($2) AND end_at >= $3 AND start_at <= $4";
-        let mut results = Vec::new();
-        for row in db
-            .query(
-                &db.prepare_cached(&sql).await?,
-                &[
-                    &filter.id,
-                    &filter.name,
-                    filter.time.as_ref().unwrap().start(),
-                    filter.time.as_ref().unwrap().end(),
-                ],
-            )
-            .await?
-        {
-            results.push(fields.load_from_row(row, Some(filter.clone()))?);
-        }
-        Ok(results)
-    }
-    /// Deletes data for the specified filters, returning it to the caller.
-    ///
-    /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
-    pub async fn delete(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        mut filter: Filter,
-        fields: impl TryInto<Fields>,
-    ) -> anyhow::Result<Vec<CompressedSerdes>> {
-        let mut fields = fields
-            .try_into()
-            .map_err(|_| anyhow::Error::msg("unknown field"))?;
-        fields.merge_filter(&filter);
-        if filter.id.is_empty() {
-            return Err(anyhow::Error::msg("id".to_string() + " is required"));
-        }
-        if filter.name.is_empty() {
-            return Err(anyhow::Error::msg("name".to_string() + " is required"));
-        }
-        if filter.time.is_none() {
-            return Err(anyhow::Error::msg("time".to_string() + " is required"));
-        }
-        filter.range_truncate()?;
-        let sql = "DELETE FROM ".to_string() + "serdes" + " WHERE "
-            + "id = ANY($1) AND name = ANY($2) AND end_at >= $3 AND start_at <= $4"
-            + " RETURNING " + fields.select().as_str();
-        let mut results = Vec::new();
-        for row in db
-            .query(
-                &db.prepare_cached(&sql).await?,
-                &[
-                    &filter.id,
-                    &filter.name,
-                    filter.time.as_ref().unwrap().start(),
-                    filter.time.as_ref().unwrap().end(),
-                ],
-            )
-            .await?
-        {
-            results.push(fields.load_from_row(row, None)?);
-        }
-        Ok(results)
-    }
-    /// Decompresses a group of data points.
-    pub fn decompress(self) -> anyhow::Result<Vec<Serde>> {
-        let mut results = Vec::new();
-        let time: Vec<u64> = if self.time.is_empty() {
-            Vec::new()
-        } else {
-            ::pco::standalone::simple_decompress(&self.time)?
-        };
-        let mut description = serde_decompress::<String>(&self.description);
-        let mut tags = serde_decompress::<Vec<String>>(&self.tags);
-        let mut nums: std::vec::IntoIter<Vec<i32>> = pco_decompress_nested(self.nums)?
-            .into_iter();
-        let mut map = serde_decompress::<BTreeMap<String, String>>(&self.map);
-        let mut json = serde_decompress::<serde_json::Value>(&self.json);
-        let mut model = serde_decompress::<Option<Box<Serde>>>(&self.model);
-        let len = [time.len()].into_iter().max().unwrap_or(0);
-        for index in 0..len {
-            let row = Serde {
-                id: self.id.clone(),
-                name: self.name.clone(),
-                time: chrono::DateTime::from_timestamp_micros(
-                        time.get(index).cloned().unwrap_or_default() as i64,
-                    )
-                    .unwrap(),
-                description: description.next().transpose()?.unwrap_or_default(),
-                tags: tags.next().transpose()?.unwrap_or_default(),
-                nums: nums.next().unwrap_or_default(),
-                map: map.next().transpose()?.unwrap_or_default(),
-                json: json.next().transpose()?.unwrap_or_default(),
-                model: model.next().transpose()?.unwrap_or_default(),
-            };
-            if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
-                results.push(row);
-            }
-        }
-        Ok(results)
-    }
-    /// Writes the data to disk.
-    pub async fn store(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<Serde>,
-    ) -> anyhow::Result<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows
-                .entry((row.id.clone(), row.name.clone()))
-                .or_default()
-                .push(row);
-        }
-        let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::UUID,
-            tokio_postgres::types::Type::TEXT,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            let time: Vec<_> = rows.iter().map(|s| s.time).collect();
-            let start_at = *time.iter().min().unwrap();
-            let end_at = *time.iter().max().unwrap();
-            let time: Vec<u64> = time
-                .into_iter()
-                .map(|t| t.timestamp_micros() as u64)
-                .collect();
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &rows[0].id,
-                        &rows[0].name,
-                        &start_at,
-                        &end_at,
-                        &::pco::standalone::simple_compress(
-                                &time,
-                                &::pco::ChunkConfig::default(),
-                            )
-                            .unwrap(),
-                        &serde_compress(
-                            rows
-                                .iter()
-                                .map(|r| r.description.clone())
-                                .collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.tags.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &pco_compress_nested(
-                            rows
-                                .iter()
-                                .map(|r| r.nums.iter().map(|v| *v).collect::<Vec<_>>())
-                                .collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.map.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.json.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.model.clone()).collect::<Vec<_>>(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-    /// Writes the data to disk, with the provided grouping closure applied.
-    ///
-    /// This can be used to improve the compression ratio and reduce read IO, for example
-    /// by compacting real-time data into a single row per hour / day / week.
-    pub async fn store_grouped<F, R>(
-        db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
-        rows: Vec<Serde>,
-        grouping: F,
-    ) -> anyhow::Result<()>
-    where
-        F: Fn(&Serde) -> R,
-        R: Eq + std::hash::Hash,
-    {
-        if rows.is_empty() {
-            return Ok(());
-        }
-        let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
-        for row in rows {
-            grouped_rows
-                .entry((row.id.clone(), row.name.clone(), grouping(&row)))
-                .or_default()
-                .push(row);
-        }
-        let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
-        let types = &[
-            tokio_postgres::types::Type::UUID,
-            tokio_postgres::types::Type::TEXT,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::TIMESTAMPTZ,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-            tokio_postgres::types::Type::BYTEA,
-        ];
-        let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
-        let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(stmt, types);
-        let mut writer = writer;
-        #[allow(unused_mut)]
-        let mut writer = unsafe {
-            ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
-        };
-        for rows in grouped_rows.into_values() {
-            let time: Vec<_> = rows.iter().map(|s| s.time).collect();
-            let start_at = *time.iter().min().unwrap();
-            let end_at = *time.iter().max().unwrap();
-            let time: Vec<u64> = time
-                .into_iter()
-                .map(|t| t.timestamp_micros() as u64)
-                .collect();
-            writer
-                .as_mut()
-                .write(
-                    &[
-                        &rows[0].id,
-                        &rows[0].name,
-                        &start_at,
-                        &end_at,
-                        &::pco::standalone::simple_compress(
-                                &time,
-                                &::pco::ChunkConfig::default(),
-                            )
-                            .unwrap(),
-                        &serde_compress(
-                            rows
-                                .iter()
-                                .map(|r| r.description.clone())
-                                .collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.tags.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &pco_compress_nested(
-                            rows
-                                .iter()
-                                .map(|r| r.nums.iter().map(|v| *v).collect::<Vec<_>>())
-                                .collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.map.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.json.clone()).collect::<Vec<_>>(),
-                        )?,
-                        &serde_compress(
-                            rows.iter().map(|r| r.model.clone()).collect::<Vec<_>>(),
-                        )?,
-                    ],
-                )
-                .await?;
-        }
-        writer.finish().await?;
-        Ok(())
-    }
-}
-#[serde(deny_unknown_fields)]
+#[serde(crate = "::pco_store::__private::serde", deny_unknown_fields)]
 /// Generated by pco_store to specify filters when loading [Serde]
-pub struct Filter {
+pub struct SerdeFilter {
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub id: Vec<Uuid>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub name: Vec<String>,
-    #[serde(deserialize_with = "deserialize_time_range")]
+    #[serde(
+        deserialize_with = "::pco_store::__private::deserialize_time_range::<_, _, DateTime < Utc >>"
+    )]
     pub time: Option<std::ops::RangeInclusive<DateTime<Utc>>>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub description: Vec<String>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub tags: Vec<Vec<String>>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub nums: Vec<Vec<i32>>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub map: Vec<BTreeMap<String, String>>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub json: Vec<serde_json::Value>,
     #[serde(default)]
-    #[serde_as(deserialize_as = "serde_with::DefaultOnNull<serde_with::OneOrMany<_>>")]
+    #[serde_as(
+        deserialize_as = "::pco_store::__private::serde_with::DefaultOnNull<::pco_store::__private::serde_with::OneOrMany<_>>"
+    )]
     #[serde(
-        deserialize_with = ":: serde_with :: As :: < serde_with :: DefaultOnNull < serde_with :: OneOrMany\n< :: serde_with :: Same > > > :: deserialize"
+        deserialize_with = ":: pco_store :: __private :: serde_with :: As :: < :: pco_store :: __private\n:: serde_with :: DefaultOnNull < :: pco_store :: __private :: serde_with ::\nOneOrMany < :: pco_store :: __private :: serde_with :: Same > > > ::\ndeserialize"
     )]
     pub model: Vec<Option<Box<Serde>>>,
 }
 #[automatically_derived]
-impl ::core::fmt::Debug for Filter {
+impl ::core::fmt::Debug for SerdeFilter {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         let names: &'static _ = &[
@@ -1055,14 +767,19 @@ impl ::core::fmt::Debug for Filter {
             &self.json,
             &&self.model,
         ];
-        ::core::fmt::Formatter::debug_struct_fields_finish(f, "Filter", names, values)
+        ::core::fmt::Formatter::debug_struct_fields_finish(
+            f,
+            "SerdeFilter",
+            names,
+            values,
+        )
     }
 }
 #[automatically_derived]
-impl ::core::default::Default for Filter {
+impl ::core::default::Default for SerdeFilter {
     #[inline]
-    fn default() -> Filter {
-        Filter {
+    fn default() -> SerdeFilter {
+        SerdeFilter {
             id: ::core::default::Default::default(),
             name: ::core::default::Default::default(),
             time: ::core::default::Default::default(),
@@ -1083,10 +800,9 @@ impl ::core::default::Default for Filter {
     clippy::absolute_paths,
 )]
 const _: () = {
-    #[allow(unused_extern_crates, clippy::useless_attribute)]
-    extern crate serde as _serde;
+    use ::pco_store::__private::serde as _serde;
     #[automatically_derived]
-    impl<'de> _serde::Deserialize<'de> for Filter {
+    impl<'de> _serde::Deserialize<'de> for SerdeFilter {
         fn deserialize<__D>(
             __deserializer: __D,
         ) -> _serde::__private228::Result<Self, __D::Error>
@@ -1216,19 +932,19 @@ const _: () = {
             }
             #[doc(hidden)]
             struct __Visitor<'de> {
-                marker: _serde::__private228::PhantomData<Filter>,
+                marker: _serde::__private228::PhantomData<SerdeFilter>,
                 lifetime: _serde::__private228::PhantomData<&'de ()>,
             }
             #[automatically_derived]
             impl<'de> _serde::de::Visitor<'de> for __Visitor<'de> {
-                type Value = Filter;
+                type Value = SerdeFilter;
                 fn expecting(
                     &self,
                     __formatter: &mut _serde::__private228::Formatter,
                 ) -> _serde::__private228::fmt::Result {
                     _serde::__private228::Formatter::write_str(
                         __formatter,
-                        "struct Filter",
+                        "struct SerdeFilter",
                     )
                 }
                 #[inline]
@@ -1243,7 +959,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<Uuid>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1255,9 +971,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1281,7 +999,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<String>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1293,9 +1011,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1319,7 +1039,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Option<std::ops::RangeInclusive<DateTime<Utc>>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1331,7 +1051,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: deserialize_time_range(__deserializer)?,
+                                    value: ::pco_store::__private::deserialize_time_range::<
+                                        _,
+                                        _,
+                                        DateTime<Utc>,
+                                    >(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
                                     lifetime: _serde::__private228::PhantomData,
                                 })
@@ -1349,7 +1073,7 @@ const _: () = {
                             return _serde::__private228::Err(
                                 _serde::de::Error::invalid_length(
                                     2usize,
-                                    &"struct Filter with 9 elements",
+                                    &"struct SerdeFilter with 9 elements",
                                 ),
                             );
                         }
@@ -1358,7 +1082,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<String>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1370,9 +1094,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1396,7 +1122,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<Vec<String>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1408,9 +1134,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1434,7 +1162,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<Vec<i32>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1446,9 +1174,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1472,7 +1202,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<BTreeMap<String, String>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1484,9 +1214,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1510,7 +1242,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<serde_json::Value>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1522,9 +1254,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1548,7 +1282,7 @@ const _: () = {
                         #[doc(hidden)]
                         struct __DeserializeWith<'de> {
                             value: Vec<Option<Box<Serde>>>,
-                            phantom: _serde::__private228::PhantomData<Filter>,
+                            phantom: _serde::__private228::PhantomData<SerdeFilter>,
                             lifetime: _serde::__private228::PhantomData<&'de ()>,
                         }
                         #[automatically_derived]
@@ -1560,9 +1294,11 @@ const _: () = {
                                 __D: _serde::Deserializer<'de>,
                             {
                                 _serde::__private228::Ok(__DeserializeWith {
-                                    value: ::serde_with::As::<
-                                        serde_with::DefaultOnNull<
-                                            serde_with::OneOrMany<::serde_with::Same>,
+                                    value: ::pco_store::__private::serde_with::As::<
+                                        ::pco_store::__private::serde_with::DefaultOnNull<
+                                            ::pco_store::__private::serde_with::OneOrMany<
+                                                ::pco_store::__private::serde_with::Same,
+                                            >,
                                         >,
                                     >::deserialize(__deserializer)?,
                                     phantom: _serde::__private228::PhantomData,
@@ -1582,7 +1318,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(SerdeFilter {
                         id: __field0,
                         name: __field1,
                         time: __field2,
@@ -1633,7 +1369,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<Uuid>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1646,9 +1382,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1676,7 +1414,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<String>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1689,9 +1427,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1719,7 +1459,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Option<std::ops::RangeInclusive<DateTime<Utc>>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1732,7 +1472,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: deserialize_time_range(__deserializer)?,
+                                                value: ::pco_store::__private::deserialize_time_range::<
+                                                    _,
+                                                    _,
+                                                    DateTime<Utc>,
+                                                >(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
                                                 lifetime: _serde::__private228::PhantomData,
                                             })
@@ -1760,7 +1504,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<String>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1773,9 +1517,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1803,7 +1549,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<Vec<String>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1816,9 +1562,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1846,7 +1594,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<Vec<i32>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1859,9 +1607,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1889,7 +1639,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<BTreeMap<String, String>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1902,9 +1652,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1932,7 +1684,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<serde_json::Value>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1945,9 +1697,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -1975,7 +1729,7 @@ const _: () = {
                                     #[doc(hidden)]
                                     struct __DeserializeWith<'de> {
                                         value: Vec<Option<Box<Serde>>>,
-                                        phantom: _serde::__private228::PhantomData<Filter>,
+                                        phantom: _serde::__private228::PhantomData<SerdeFilter>,
                                         lifetime: _serde::__private228::PhantomData<&'de ()>,
                                     }
                                     #[automatically_derived]
@@ -1988,9 +1742,11 @@ const _: () = {
                                             __D: _serde::Deserializer<'de>,
                                         {
                                             _serde::__private228::Ok(__DeserializeWith {
-                                                value: ::serde_with::As::<
-                                                    serde_with::DefaultOnNull<
-                                                        serde_with::OneOrMany<::serde_with::Same>,
+                                                value: ::pco_store::__private::serde_with::As::<
+                                                    ::pco_store::__private::serde_with::DefaultOnNull<
+                                                        ::pco_store::__private::serde_with::OneOrMany<
+                                                            ::pco_store::__private::serde_with::Same,
+                                                        >,
                                                     >,
                                                 >::deserialize(__deserializer)?,
                                                 phantom: _serde::__private228::PhantomData,
@@ -2066,7 +1822,7 @@ const _: () = {
                             _serde::__private228::Default::default()
                         }
                     };
-                    _serde::__private228::Ok(Filter {
+                    _serde::__private228::Ok(SerdeFilter {
                         id: __field0,
                         name: __field1,
                         time: __field2,
@@ -2093,10 +1849,10 @@ const _: () = {
             ];
             _serde::Deserializer::deserialize_struct(
                 __deserializer,
-                "Filter",
+                "SerdeFilter",
                 FIELDS,
                 __Visitor {
-                    marker: _serde::__private228::PhantomData::<Filter>,
+                    marker: _serde::__private228::PhantomData::<SerdeFilter>,
                     lifetime: _serde::__private228::PhantomData,
                 },
             )
@@ -2104,10 +1860,10 @@ const _: () = {
     }
 };
 #[automatically_derived]
-impl ::core::clone::Clone for Filter {
+impl ::core::clone::Clone for SerdeFilter {
     #[inline]
-    fn clone(&self) -> Filter {
-        Filter {
+    fn clone(&self) -> SerdeFilter {
+        SerdeFilter {
             id: ::core::clone::Clone::clone(&self.id),
             name: ::core::clone::Clone::clone(&self.name),
             time: ::core::clone::Clone::clone(&self.time),
@@ -2121,80 +1877,19 @@ impl ::core::clone::Clone for Filter {
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Filter {}
+impl ::core::marker::StructuralPartialEq for SerdeFilter {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Filter {
+impl ::core::cmp::PartialEq for SerdeFilter {
     #[inline]
-    fn eq(&self, other: &Filter) -> bool {
+    fn eq(&self, other: &SerdeFilter) -> bool {
         self.id == other.id && self.name == other.name && self.time == other.time
             && self.description == other.description && self.tags == other.tags
             && self.nums == other.nums && self.map == other.map
             && self.json == other.json && self.model == other.model
     }
 }
-impl Filter {
-    /// Builds new filter with the required fields defined by `group_by` and `timestamp`
-    pub fn new(
-        id: &[Uuid],
-        name: &[String],
-        time: std::ops::RangeInclusive<DateTime<Utc>>,
-    ) -> Self {
-        Self {
-            id: id.into(),
-            name: name.into(),
-            time: Some(time),
-            ..Self::default()
-        }
-    }
-    fn matches(&self, row: &Serde) -> bool {
-        (self.id.is_empty() || self.id.contains(&row.id))
-            && (self.name.is_empty() || self.name.contains(&row.name))
-            && self.time.as_ref().map(|t| t.contains(&row.time)) != Some(false)
-            && (self.description.is_empty()
-                || self.description.contains(&row.description))
-            && (self.tags.is_empty() || self.tags.contains(&row.tags))
-            && (self.nums.is_empty() || self.nums.contains(&row.nums))
-            && (self.map.is_empty() || self.map.contains(&row.map))
-            && (self.json.is_empty() || self.json.contains(&row.json))
-            && (self.model.is_empty() || self.model.contains(&row.model))
-    }
-    /// Convenience function to unwrap the timestamp range lower and upper bounds
-    pub fn range_bounds(&self) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)> {
-        use anyhow::Context;
-        let timestamp = self.time.clone().context("no timestamp")?;
-        Ok((*timestamp.start(), *timestamp.end()))
-    }
-    /// Convenience function to return the amount of time the filter covers
-    pub fn range_duration(&self) -> anyhow::Result<chrono::Duration> {
-        let (start, end) = self.range_bounds()?;
-        Ok(end - start)
-    }
-    /// Shifts the filtered time range. This for example makes it easier
-    /// to perform two queries: once for "today", and one for "today, 7 days ago".
-    /// In that example the second query would do `filter.shift(Duration::days(-7))`
-    pub fn range_shift(&mut self, duration: chrono::Duration) -> anyhow::Result<()> {
-        use std::ops::Add;
-        let (start, end) = self.range_bounds()?;
-        self.time = Some(start.add(duration)..=end.add(duration));
-        Ok(())
-    }
-    /// Postgres doesn't support nanosecond precision and nor does MacOS, so this
-    /// truncates nanosecond precision for timestamp comparisons
-    fn range_truncate(&mut self) -> anyhow::Result<()> {
-        let (start, end) = self.range_bounds()?;
-        self.time = Some(Self::truncate_nanos(start)?..=Self::truncate_nanos(end)?);
-        Ok(())
-    }
-    fn truncate_nanos(time: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
-        use anyhow::Context;
-        Ok(
-            chrono::DateTime::from_timestamp_micros(time.timestamp_micros())
-                .context("out of range")?,
-        )
-    }
-}
 /// Generated by pco_store to choose which fields to decompress when loading [Serde]
-pub struct Fields {
+pub struct SerdeFields {
     id: bool,
     name: bool,
     time: bool,
@@ -2207,19 +1902,19 @@ pub struct Fields {
 }
 #[automatically_derived]
 #[doc(hidden)]
-unsafe impl ::core::clone::TrivialClone for Fields {}
+unsafe impl ::core::clone::TrivialClone for SerdeFields {}
 #[automatically_derived]
-impl ::core::clone::Clone for Fields {
+impl ::core::clone::Clone for SerdeFields {
     #[inline]
-    fn clone(&self) -> Fields {
+    fn clone(&self) -> SerdeFields {
         let _: ::core::clone::AssertParamIsClone<bool>;
         *self
     }
 }
 #[automatically_derived]
-impl ::core::marker::Copy for Fields {}
+impl ::core::marker::Copy for SerdeFields {}
 #[automatically_derived]
-impl ::core::fmt::Debug for Fields {
+impl ::core::fmt::Debug for SerdeFields {
     #[inline]
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         let names: &'static _ = &[
@@ -2244,373 +1939,1050 @@ impl ::core::fmt::Debug for Fields {
             &self.json,
             &&self.model,
         ];
-        ::core::fmt::Formatter::debug_struct_fields_finish(f, "Fields", names, values)
+        ::core::fmt::Formatter::debug_struct_fields_finish(
+            f,
+            "SerdeFields",
+            names,
+            values,
+        )
     }
 }
 #[automatically_derived]
-impl ::core::marker::StructuralPartialEq for Fields {}
+impl ::core::marker::StructuralPartialEq for SerdeFields {}
 #[automatically_derived]
-impl ::core::cmp::PartialEq for Fields {
+impl ::core::cmp::PartialEq for SerdeFields {
     #[inline]
-    fn eq(&self, other: &Fields) -> bool {
+    fn eq(&self, other: &SerdeFields) -> bool {
         self.id == other.id && self.name == other.name && self.time == other.time
             && self.description == other.description && self.tags == other.tags
             && self.nums == other.nums && self.map == other.map
             && self.json == other.json && self.model == other.model
     }
 }
-impl Fields {
-    pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
-        fields.try_into().map_err(|e| anyhow::Error::msg(e))
-    }
-    pub fn required() -> Self {
-        Self {
-            id: true,
-            name: true,
-            time: true,
-            description: false,
-            tags: false,
-            nums: false,
-            map: false,
-            json: false,
-            model: false,
+const _: () = {
+    #[allow(unused_imports)]
+    use ::pco_store::__private::{
+        ahash, anyhow, deadpool_postgres, futures, pco, rmp_serde, serde, serde_bytes,
+        serde_with, tokio, tokio_postgres, zstd,
+    };
+    use ::pco_store::__private::*;
+    type Filter = SerdeFilter;
+    type Fields = SerdeFields;
+    impl CompressedSerdes {
+        /// Loads data for the specified filters.
+        pub async fn load(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedSerdes>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            if filter.id.is_empty() {
+                return Err(anyhow::Error::msg("id".to_string() + " is required"));
+            }
+            if filter.name.is_empty() {
+                return Err(anyhow::Error::msg("name".to_string() + " is required"));
+            }
+            if filter.time.is_none() {
+                return Err(anyhow::Error::msg("time".to_string() + " is required"));
+            }
+            filter.range_truncate()?;
+            let sql = "SELECT ".to_string() + fields.select().as_str() + " FROM "
+                + "serdes" + " WHERE "
+                + "id = ANY($1) AND name = ANY($2) AND end_at >= $3 AND start_at <= $4";
+            let mut results = Vec::new();
+            for row in db
+                .query(
+                    &db.prepare_cached(&sql).await?,
+                    &[
+                        &filter.id,
+                        &filter.name,
+                        &std::time::SystemTime::from(
+                            (*filter.time.as_ref().unwrap().start()),
+                        ),
+                        &std::time::SystemTime::from(
+                            (*filter.time.as_ref().unwrap().end()),
+                        ),
+                    ],
+                )
+                .await?
+            {
+                results.push(fields.load_from_row(row, Some(filter.clone()))?);
+            }
+            Ok(results)
         }
-    }
-    fn merge_filter(&mut self, filter: &Filter) {
-        (!filter.description.is_empty()).then(|| self.description = true);
-        (!filter.tags.is_empty()).then(|| self.tags = true);
-        (!filter.nums.is_empty()).then(|| self.nums = true);
-        (!filter.map.is_empty()).then(|| self.map = true);
-        (!filter.json.is_empty()).then(|| self.json = true);
-        (!filter.model.is_empty()).then(|| self.model = true);
-    }
-    fn select(&self) -> String {
-        let mut fields = Vec::new();
-        self.id.then(|| fields.push("id"));
-        self.name.then(|| fields.push("name"));
-        self.time.then(|| fields.push("time"));
-        self.description.then(|| fields.push("description"));
-        self.tags.then(|| fields.push("tags"));
-        self.nums.then(|| fields.push("nums"));
-        self.map.then(|| fields.push("map"));
-        self.json.then(|| fields.push("json"));
-        self.model.then(|| fields.push("model"));
-        fields.join(", ")
-    }
-    fn load_from_row(
-        &self,
-        row: tokio_postgres::Row,
-        filter: Option<Filter>,
-    ) -> anyhow::Result<CompressedSerdes> {
-        let mut index = 0;
-        Ok(CompressedSerdes {
-            filter,
-            id: if self.id {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            name: if self.name {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            time: if self.time {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            description: if self.description {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            tags: if self.tags {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            nums: if self.nums {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            map: if self.map {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            json: if self.json {
-                let v = row.get(index);
-                index += 1;
-                v
-            } else {
-                Default::default()
-            },
-            model: if self.model {
-                let v = row.get(index);
+        /// Deletes data for the specified filters, returning it to the caller.
+        ///
+        /// Note that all rows are returned from [decompress][Self::decompress] even if post-decompress filters would normally apply.
+        pub async fn delete(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            mut filter: Filter,
+            fields: impl TryInto<Fields>,
+        ) -> anyhow::Result<Vec<CompressedSerdes>> {
+            let mut fields = fields
+                .try_into()
+                .map_err(|_| anyhow::Error::msg("unknown field"))?;
+            fields.merge_filter(&filter);
+            if filter.id.is_empty() {
+                return Err(anyhow::Error::msg("id".to_string() + " is required"));
+            }
+            if filter.name.is_empty() {
+                return Err(anyhow::Error::msg("name".to_string() + " is required"));
+            }
+            if filter.time.is_none() {
+                return Err(anyhow::Error::msg("time".to_string() + " is required"));
+            }
+            filter.range_truncate()?;
+            let sql = "DELETE FROM ".to_string() + "serdes" + " WHERE "
+                + "id = ANY($1) AND name = ANY($2) AND end_at >= $3 AND start_at <= $4"
+                + " RETURNING " + fields.select().as_str();
+            let mut results = Vec::new();
+            for row in db
+                .query(
+                    &db.prepare_cached(&sql).await?,
+                    &[
+                        &filter.id,
+                        &filter.name,
+                        &std::time::SystemTime::from(
+                            (*filter.time.as_ref().unwrap().start()),
+                        ),
+                        &std::time::SystemTime::from(
+                            (*filter.time.as_ref().unwrap().end()),
+                        ),
+                    ],
+                )
+                .await?
+            {
+                results.push(fields.load_from_row(row, None)?);
+            }
+            Ok(results)
+        }
+        /// Decompresses a group of data points.
+        pub fn decompress(self) -> anyhow::Result<Vec<Serde>> {
+            let mut results = Vec::new();
+            let time: Vec<i64> = pco_decompress_timestamps(&self.time, 1000000i64)?;
+            let mut description: Values<'_, String> = (&&&Column::<
+                String,
+            >(std::marker::PhantomData))
+                .kind()
+                .decompress(&self.description)?
+                .into();
+            let mut tags: Values<'_, Vec<String>> = (&&&Column::<
+                Vec<String>,
+            >(std::marker::PhantomData))
+                .kind()
+                .decompress(&self.tags)?
+                .into();
+            let mut nums: std::vec::IntoIter<Vec<i32>> = pco_decompress_nested(
+                    self.nums,
+                )?
+                .into_iter();
+            let mut map: Values<'_, BTreeMap<String, String>> = (&&&Column::<
+                BTreeMap<String, String>,
+            >(std::marker::PhantomData))
+                .kind()
+                .decompress(&self.map)?
+                .into();
+            let mut json: Values<'_, serde_json::Value> = (&&&Column::<
+                serde_json::Value,
+            >(std::marker::PhantomData))
+                .kind()
+                .decompress(&self.json)?
+                .into();
+            let mut model: Values<'_, Option<Box<Serde>>> = (&&&Column::<
+                Option<Box<Serde>>,
+            >(std::marker::PhantomData))
+                .kind()
+                .decompress(&self.model)?
+                .into();
+            let len = [
+                Some(time.len()),
+                description.known_len(),
+                tags.known_len(),
+                Some(nums.len()),
+                map.known_len(),
+                json.known_len(),
+                model.known_len(),
+            ]
+                .into_iter()
+                .flatten()
+                .max()
+                .filter(|len| *len > 0);
+            let mut index = 0;
+            while len
+                .map_or_else(
+                    || {
+                        [
+                            description.has_next(),
+                            tags.has_next(),
+                            map.has_next(),
+                            json.has_next(),
+                            model.has_next(),
+                        ]
+                            .contains(&true)
+                    },
+                    |len| index < len,
+                )
+            {
+                let row = Serde {
+                    id: self.id.clone(),
+                    name: self.name.clone(),
+                    time: chrono::DateTime::from_timestamp_micros(
+                            time.get(index).cloned().unwrap_or_default(),
+                        )
+                        .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    description: description.next().transpose()?.unwrap_or_default(),
+                    tags: tags.next().transpose()?.unwrap_or_default(),
+                    nums: nums.next().unwrap_or_default(),
+                    map: map.next().transpose()?.unwrap_or_default(),
+                    json: json.next().transpose()?.unwrap_or_default(),
+                    model: model.next().transpose()?.unwrap_or_default(),
+                };
+                if self.filter.as_ref().map(|f| f.matches(&row)) != Some(false) {
+                    results.push(row);
+                }
                 index += 1;
-                v
-            } else {
-                Default::default()
-            },
-        })
-    }
-}
-impl Default for Fields {
-    fn default() -> Self {
-        Self {
-            id: true,
-            name: true,
-            time: true,
-            description: true,
-            tags: true,
-            nums: true,
-            map: true,
-            json: true,
-            model: true,
+            }
+            Ok(results)
         }
-    }
-}
-impl TryFrom<&[&str]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
-        let mut fields = Self::required();
-        for s in input {
-            match *s {
-                "id" => fields.id = true,
-                "name" => fields.name = true,
-                "time" => fields.time = true,
-                "description" => fields.description = true,
-                "tags" => fields.tags = true,
-                "nums" => fields.nums = true,
-                "map" => fields.map = true,
-                "json" => fields.json = true,
-                "model" => fields.model = true,
-                _ => return Err("unknown field"),
+        /// Decompresses many groups in parallel, returning their data points in the same order as the groups.
+        pub async fn decompress_all(groups: Vec<Self>) -> anyhow::Result<Vec<Serde>> {
+            if groups.is_empty() {
+                return Ok(Vec::new());
+            }
+            let threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let chunk_size = groups.len().div_ceil(threads);
+            let mut groups = groups.into_iter();
+            let mut tasks = Vec::new();
+            loop {
+                let chunk: Vec<Self> = groups.by_ref().take(chunk_size).collect();
+                if chunk.is_empty() {
+                    break;
+                }
+                tasks
+                    .push(
+                        tokio::task::spawn_blocking(move || {
+                            chunk
+                                .into_iter()
+                                .map(Self::decompress)
+                                .collect::<anyhow::Result<Vec<_>>>()
+                        }),
+                    );
+            }
+            let mut groups: Vec<Vec<Serde>> = Vec::new();
+            for task in tasks {
+                groups.extend(task.await??);
             }
+            Ok(groups.into_iter().flatten().collect())
+        }
+        /// Decompresses many groups in parallel like [decompress_all][Self::decompress_all], returning their data points sorted by `time`.
+        ///
+        /// Data points with the same timestamp keep the order of their groups.
+        pub async fn decompress_all_sorted(
+            groups: Vec<Self>,
+        ) -> anyhow::Result<Vec<Serde>> {
+            let mut rows = Self::decompress_all(groups).await?;
+            let rows = tokio::task::spawn_blocking(move || {
+                rows.sort_by(|a, b| a.time.cmp(&b.time));
+                rows
+            });
+            Ok(rows.await?)
+        }
+        /// Compresses the fields of a group, returning the rows along with their timestamp range.
+        fn compress_group(
+            rows: Vec<Serde>,
+        ) -> anyhow::Result<(Vec<Serde>, (i64, i64), Vec<Vec<u8>>)> {
+            let mut time: Vec<i64> = Vec::with_capacity(rows.len());
+            for s in rows.iter() {
+                time.push(s.time.timestamp_micros());
+            }
+            let bounds = (*time.iter().min().unwrap(), *time.iter().max().unwrap());
+            let compressed: Vec<Vec<u8>> = <[_]>::into_vec(
+                ::alloc::boxed::box_new([
+                    pco::standalone::simple_compress(
+                        &time,
+                        &pco::ChunkConfig::default(),
+                    )?,
+                    (&&&Column::<String>(std::marker::PhantomData))
+                        .kind()
+                        .compress(rows.iter().map(|r| &r.description))?,
+                    (&&&Column::<Vec<String>>(std::marker::PhantomData))
+                        .kind()
+                        .compress(rows.iter().map(|r| &r.tags))?,
+                    pco_compress_nested(
+                        rows
+                            .iter()
+                            .map(|r| r.nums.iter().map(|v| *v).collect::<Vec<_>>())
+                            .collect::<Vec<_>>(),
+                    )?,
+                    (&&&Column::<BTreeMap<String, String>>(std::marker::PhantomData))
+                        .kind()
+                        .compress(rows.iter().map(|r| &r.map))?,
+                    (&&&Column::<serde_json::Value>(std::marker::PhantomData))
+                        .kind()
+                        .compress(rows.iter().map(|r| &r.json))?,
+                    (&&&Column::<Option<Box<Serde>>>(std::marker::PhantomData))
+                        .kind()
+                        .compress(rows.iter().map(|r| &r.model))?,
+                ]),
+            );
+            Ok((rows, bounds, compressed))
+        }
+        /// Compresses each part of the data that will be written as a row.
+        async fn compress_parts(
+            parts: Vec<Vec<Serde>>,
+        ) -> anyhow::Result<Vec<(Vec<Serde>, (i64, i64), Vec<Vec<u8>>)>> {
+            parts.into_iter().map(Self::compress_group).collect()
+        }
+        /// Writes the data to disk.
+        pub async fn store(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = Serde>,
+        ) -> anyhow::Result<()> {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows
+                    .entry((row.id.clone(), row.name.clone()))
+                    .or_default()
+                    .push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::UUID,
+                tokio_postgres::types::Type::TEXT,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<Serde>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].id,
+                                &rows[0].name,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk, with the provided grouping closure applied.
+        ///
+        /// This can be used to improve the compression ratio and reduce read IO, for example
+        /// by compacting real-time data into a single row per hour / day / week.
+        pub async fn store_grouped<F, R>(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl IntoIterator<Item = Serde>,
+            grouping: F,
+        ) -> anyhow::Result<()>
+        where
+            F: Fn(&Serde) -> R,
+            R: Eq + std::hash::Hash,
+        {
+            let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
+            for row in rows {
+                grouped_rows
+                    .entry((row.id.clone(), row.name.clone(), grouping(&row)))
+                    .or_default()
+                    .push(row);
+            }
+            if grouped_rows.is_empty() {
+                return Ok(());
+            }
+            let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::UUID,
+                tokio_postgres::types::Type::TEXT,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = grouped_rows.into_values();
+            let mut parts: Vec<Vec<Serde>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].id,
+                                &rows[0].name,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Writes the data to disk as it's received from a stream, for bulk imports that don't fit in memory.
+        ///
+        /// Rows are grouped the same way as [store][Self::store], and each group is written once it has `group_size`
+        /// rows. Any remaining groups are written when the stream ends. Duplicates are only removed within a group.
+        pub async fn store_stream(
+            db: &impl ::std::ops::Deref<Target = deadpool_postgres::ClientWrapper>,
+            rows: impl futures::Stream<Item = Serde>,
+            group_size: usize,
+        ) -> anyhow::Result<()> {
+            if group_size == 0 {
+                return Err(anyhow::Error::msg("group_size must be greater than zero"));
+            }
+            let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::UUID,
+                tokio_postgres::types::Type::TEXT,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = db.copy_in(&db.prepare_cached(&sql).await?).await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let mut rows = rows;
+            #[allow(unused_mut)]
+            let mut rows = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut rows)
+            };
+            let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
+            while let Some(row) = futures::StreamExt::next(&mut rows).await {
+                let group = grouped_rows
+                    .entry((row.id.clone(), row.name.clone()))
+                    .or_default();
+                group.push(row);
+                if group.len() >= group_size {
+                    let rows = std::mem::take(group);
+                    let groups = std::iter::once(rows);
+                    let mut parts: Vec<Vec<Serde>> = { groups.collect() };
+                    while !parts.is_empty() {
+                        for (rows, bounds, compressed) in Self::compress_parts(
+                                std::mem::take(&mut parts),
+                            )
+                            .await?
+                        {
+                            let start_at = std::time::SystemTime::from(
+                                chrono::DateTime::from_timestamp_micros(bounds.0)
+                                    .ok_or_else(|| anyhow::Error::msg(
+                                        "timestamp out of range",
+                                    ))?,
+                            );
+                            let end_at = std::time::SystemTime::from(
+                                chrono::DateTime::from_timestamp_micros(bounds.1)
+                                    .ok_or_else(|| anyhow::Error::msg(
+                                        "timestamp out of range",
+                                    ))?,
+                            );
+                            writer
+                                .as_mut()
+                                .write(
+                                    &[
+                                        &rows[0].id,
+                                        &rows[0].name,
+                                        &start_at,
+                                        &end_at,
+                                        &compressed[0usize],
+                                        &compressed[1usize],
+                                        &compressed[2usize],
+                                        &compressed[3usize],
+                                        &compressed[4usize],
+                                        &compressed[5usize],
+                                        &compressed[6usize],
+                                    ],
+                                )
+                                .await?;
+                        }
+                    }
+                }
+            }
+            let groups = grouped_rows
+                .into_values()
+                .filter(|rows| !rows.is_empty())
+                .map(|rows| { rows });
+            let mut parts: Vec<Vec<Serde>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].id,
+                                &rows[0].name,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            Ok(())
+        }
+        /// Merges the data into the existing groups for each `group_by` value and `bucket` of time, in a single transaction.
+        ///
+        /// Existing groups that fall entirely within a bucket are locked with `SELECT ... FOR UPDATE`, decompressed,
+        /// combined with the new rows and written back as one group. This keeps low-volume data compacted without
+        /// a separate rewrite job. This runs its own transaction, so it can't be called inside of another one. The
+        /// transaction is rolled back if it fails, or if the returned future is dropped before it completes.
+        pub async fn store_merge(
+            db: &mut deadpool_postgres::ClientWrapper,
+            rows: Vec<Serde>,
+            bucket: std::time::Duration,
+        ) -> anyhow::Result<()> {
+            if rows.is_empty() {
+                return Ok(());
+            }
+            let units = i64::try_from(bucket.as_nanos() as i128 / 1000i128)
+                .unwrap_or(i64::MAX);
+            if units == 0 {
+                return Err(
+                    anyhow::Error::msg(
+                        "bucket must be at least one timestamp_precision unit",
+                    ),
+                );
+            }
+            let mut grouped_rows: ahash::AHashMap<_, Vec<Serde>> = ahash::AHashMap::new();
+            for row in rows {
+                let bucket = (row.time.timestamp_micros()).div_euclid(units);
+                grouped_rows
+                    .entry((row.id.clone(), row.name.clone(), bucket))
+                    .or_default()
+                    .push(row);
+            }
+            let fields = Fields::default();
+            let select_sql = ::alloc::__export::must_use({
+                ::alloc::fmt::format(
+                    format_args!(
+                        "SELECT {0}, ctid::text FROM serdes WHERE id = $1 AND name = $2 AND start_at >= $3 AND end_at <= $4 FOR UPDATE",
+                        fields.select(),
+                    ),
+                )
+            });
+            let transaction = db.transaction().await?;
+            let mut merged_groups = Vec::new();
+            for ((.., bucket), rows) in grouped_rows {
+                let start = system_time_from_int(bucket * units, 1000i128)?;
+                let end = system_time_from_int(bucket * units + (units - 1), 1000i128)?;
+                let mut merged = Vec::new();
+                let mut ctids = Vec::new();
+                for row in transaction
+                    .query(
+                        &transaction.prepare_cached(&select_sql).await?,
+                        &[&rows[0].id, &rows[0].name, &start, &end],
+                    )
+                    .await?
+                {
+                    ctids.push(row.get::<_, String>(row.len() - 1));
+                    merged.extend(fields.load_from_row(row, None)?.decompress()?);
+                }
+                if !ctids.is_empty() {
+                    transaction
+                        .execute(
+                            &transaction
+                                .prepare_cached(
+                                    "DELETE FROM serdes WHERE id = $1 AND name = $2 AND ctid = ANY($3::text[]::tid[])",
+                                )
+                                .await?,
+                            &[&rows[0].id, &rows[0].name, &ctids],
+                        )
+                        .await?;
+                }
+                merged.extend(rows);
+                merged_groups.push(merged);
+            }
+            let sql = "COPY serdes (id, name, start_at, end_at, time, description, tags, nums, map, json, model) FROM STDIN BINARY";
+            let types = &[
+                tokio_postgres::types::Type::UUID,
+                tokio_postgres::types::Type::TEXT,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::TIMESTAMPTZ,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+                tokio_postgres::types::Type::BYTEA,
+            ];
+            let stmt = transaction
+                .copy_in(&transaction.prepare_cached(&sql).await?)
+                .await?;
+            let writer = tokio_postgres::binary_copy::BinaryCopyInWriter::new(
+                stmt,
+                types,
+            );
+            let mut writer = writer;
+            #[allow(unused_mut)]
+            let mut writer = unsafe {
+                ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut writer)
+            };
+            let groups = merged_groups.into_iter().map(|rows| { rows });
+            let mut parts: Vec<Vec<Serde>> = { groups.collect() };
+            while !parts.is_empty() {
+                for (rows, bounds, compressed) in Self::compress_parts(
+                        std::mem::take(&mut parts),
+                    )
+                    .await?
+                {
+                    let start_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.0)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    let end_at = std::time::SystemTime::from(
+                        chrono::DateTime::from_timestamp_micros(bounds.1)
+                            .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?,
+                    );
+                    writer
+                        .as_mut()
+                        .write(
+                            &[
+                                &rows[0].id,
+                                &rows[0].name,
+                                &start_at,
+                                &end_at,
+                                &compressed[0usize],
+                                &compressed[1usize],
+                                &compressed[2usize],
+                                &compressed[3usize],
+                                &compressed[4usize],
+                                &compressed[5usize],
+                                &compressed[6usize],
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            writer.finish().await?;
+            transaction.commit().await?;
+            Ok(())
         }
-        Ok(fields)
-    }
-}
-impl<const N: usize> TryFrom<&[&str; N]> for Fields {
-    type Error = &'static str;
-    fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
-        Self::try_from(&input[..])
-    }
-}
-impl TryFrom<Vec<String>> for Fields {
-    type Error = &'static str;
-    fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
-        let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
-        Self::try_from(input.as_slice())
     }
-}
-impl From<()> for Fields {
-    fn from(_: ()) -> Self {
-        Self::default()
+    impl ::pco_store::PcoStore for CompressedSerdes {
+        type Row = Serde;
+        type Filter = Filter;
+        type Fields = Fields;
+        const TABLE_NAME: &'static str = "serdes";
+        const COLUMNS: &'static [&'static str] = &[
+            "id",
+            "name",
+            "start_at",
+            "end_at",
+            "time",
+            "description",
+            "tags",
+            "nums",
+            "map",
+            "json",
+            "model",
+        ];
+        const GROUP_BY: &'static [&'static str] = &["id", "name"];
+        const TIMESTAMP: Option<&'static str> = Some("time");
+        fn store<D>(
+            db: &D,
+            rows: impl IntoIterator<Item = Serde> + Send,
+        ) -> impl std::future::Future<Output = anyhow::Result<()>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::store(db, rows)
+        }
+        fn load<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::load(db, filter, fields)
+        }
+        fn delete<D>(
+            db: &D,
+            filter: Filter,
+            fields: Fields,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Self>>> + Send
+        where
+            D: std::ops::Deref<Target = deadpool_postgres::ClientWrapper> + Sync,
+        {
+            Self::delete(db, filter, fields)
+        }
+        fn decompress(self) -> anyhow::Result<Vec<Serde>> {
+            Self::decompress(self)
+        }
+        fn decompress_all(
+            groups: Vec<Self>,
+        ) -> impl std::future::Future<Output = anyhow::Result<Vec<Serde>>> + Send {
+            Self::decompress_all(groups)
+        }
     }
-}
-impl<'de> serde::Deserialize<'de> for Fields {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_any(FieldsVisitor)
+    impl Filter {
+        /// Builds new filter with the required fields defined by `group_by` and `timestamp`
+        pub fn new(
+            id: &[Uuid],
+            name: &[String],
+            time: std::ops::RangeInclusive<DateTime<Utc>>,
+        ) -> Self {
+            Self {
+                id: id.into(),
+                name: name.into(),
+                time: Some(time),
+                ..Self::default()
+            }
+        }
+        fn matches(&self, row: &Serde) -> bool {
+            (self.id.is_empty() || self.id.contains(&row.id))
+                && (self.name.is_empty() || self.name.contains(&row.name))
+                && self.time.as_ref().map(|t| t.contains(&row.time)) != Some(false)
+                && (self.description.is_empty()
+                    || self.description.contains(&row.description))
+                && (self.tags.is_empty() || self.tags.contains(&row.tags))
+                && (self.nums.is_empty() || self.nums.contains(&row.nums))
+                && (self.map.is_empty() || self.map.contains(&row.map))
+                && (self.json.is_empty() || self.json.contains(&row.json))
+                && (self.model.is_empty() || self.model.contains(&row.model))
+        }
+        /// Convenience function to unwrap the timestamp range lower and upper bounds
+        pub fn range_bounds(&self) -> anyhow::Result<(DateTime<Utc>, DateTime<Utc>)> {
+            use anyhow::Context;
+            let timestamp = self.time.clone().context("no timestamp")?;
+            Ok((*timestamp.start(), *timestamp.end()))
+        }
+        /// Convenience function to return the amount of time the filter covers
+        pub fn range_duration(&self) -> anyhow::Result<chrono::Duration> {
+            let (start, end) = self.range_bounds()?;
+            Ok(end - start)
+        }
+        /// Shifts the filtered time range. This for example makes it easier
+        /// to perform two queries: once for "today", and one for "today, 7 days ago".
+        /// In that example the second query would do `filter.shift(Duration::days(-7))`
+        pub fn range_shift(&mut self, duration: chrono::Duration) -> anyhow::Result<()> {
+            use std::ops::Add;
+            let (start, end) = self.range_bounds()?;
+            self.time = Some(start.add(duration)..=end.add(duration));
+            Ok(())
+        }
+        /// Timestamps are stored with the `timestamp_precision` given to the macro (microseconds by default),
+        /// so this truncates the time range to that precision for timestamp comparisons
+        fn range_truncate(&mut self) -> anyhow::Result<()> {
+            let (start, end) = self.range_bounds()?;
+            self.time = Some(Self::truncate(start)?..=Self::truncate(end)?);
+            Ok(())
+        }
+        fn truncate(time: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+            let value = time.timestamp_micros();
+            let time = chrono::DateTime::from_timestamp_micros(value)
+                .ok_or_else(|| anyhow::Error::msg("timestamp out of range"))?;
+            Ok(time)
+        }
     }
-}
-struct FieldsVisitor;
-impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
-    type Value = Fields;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("an array of strings matching the struct fields")
+    impl Fields {
+        pub fn new(fields: &[&str]) -> anyhow::Result<Self> {
+            fields.try_into().map_err(|e| anyhow::Error::msg(e))
+        }
+        pub fn required() -> Self {
+            Self {
+                id: true,
+                name: true,
+                time: true,
+                description: false,
+                tags: false,
+                nums: false,
+                map: false,
+                json: false,
+                model: false,
+            }
+        }
+        fn merge_filter(&mut self, filter: &Filter) {
+            (!filter.description.is_empty()).then(|| self.description = true);
+            (!filter.tags.is_empty()).then(|| self.tags = true);
+            (!filter.nums.is_empty()).then(|| self.nums = true);
+            (!filter.map.is_empty()).then(|| self.map = true);
+            (!filter.json.is_empty()).then(|| self.json = true);
+            (!filter.model.is_empty()).then(|| self.model = true);
+        }
+        fn select(&self) -> String {
+            let mut fields = Vec::new();
+            self.id.then(|| fields.push("id"));
+            self.name.then(|| fields.push("name"));
+            self.time.then(|| fields.push("time"));
+            self.description.then(|| fields.push("description"));
+            self.tags.then(|| fields.push("tags"));
+            self.nums.then(|| fields.push("nums"));
+            self.map.then(|| fields.push("map"));
+            self.json.then(|| fields.push("json"));
+            self.model.then(|| fields.push("model"));
+            fields.join(", ")
+        }
+        fn load_from_row(
+            &self,
+            row: tokio_postgres::Row,
+            filter: Option<Filter>,
+        ) -> anyhow::Result<CompressedSerdes> {
+            let mut index = 0;
+            Ok(CompressedSerdes {
+                filter,
+                id: {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                },
+                name: {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                },
+                time: if self.time {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                description: if self.description {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                tags: if self.tags {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                nums: if self.nums {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                map: if self.map {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                json: if self.json {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+                model: if self.model {
+                    let v = row.get(index);
+                    index += 1;
+                    v
+                } else {
+                    Default::default()
+                },
+            })
+        }
     }
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let mut fields = Vec::new();
-        while let Some(field) = seq.next_element()? {
-            fields.push(field);
+    impl Default for Fields {
+        fn default() -> Self {
+            Self {
+                id: true,
+                name: true,
+                time: true,
+                description: true,
+                tags: true,
+                nums: true,
+                map: true,
+                json: true,
+                model: true,
+            }
         }
-        Fields::try_from(fields).map_err(serde::de::Error::custom)
     }
-    fn visit_unit<E>(self) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(Fields::default())
+    impl TryFrom<&[&str]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
+            let mut fields = Self::required();
+            for s in input {
+                match *s {
+                    "id" => fields.id = true,
+                    "name" => fields.name = true,
+                    "time" => fields.time = true,
+                    "description" => fields.description = true,
+                    "tags" => fields.tags = true,
+                    "nums" => fields.nums = true,
+                    "map" => fields.map = true,
+                    "json" => fields.json = true,
+                    "model" => fields.model = true,
+                    _ => return Err("unknown field"),
+                }
+            }
+            Ok(fields)
+        }
     }
-}
-/// Deserializes many different time range formats:
-/// - an array with two strings becomes a normal time range: ["a", "b"] -> a..=b
-/// - an array with one string becomes a single-value time range: ["a"] -> a..=a
-/// - a string literal becomes a single-value time range:           "a" -> a..=a
-fn deserialize_time_range<'de, D>(
-    deserializer: D,
-) -> Result<Option<std::ops::RangeInclusive<DateTime<Utc>>>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    Ok(TimeRange::deserialize(deserializer)?.0)
-}
-struct TimeRange(Option<std::ops::RangeInclusive<DateTime<Utc>>>);
-#[automatically_derived]
-impl ::core::fmt::Debug for TimeRange {
-    #[inline]
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        ::core::fmt::Formatter::debug_tuple_field1_finish(f, "TimeRange", &&self.0)
+    impl<const N: usize> TryFrom<&[&str; N]> for Fields {
+        type Error = &'static str;
+        fn try_from(input: &[&str; N]) -> Result<Self, Self::Error> {
+            Self::try_from(&input[..])
+        }
     }
-}
-#[automatically_derived]
-impl ::core::marker::StructuralPartialEq for TimeRange {}
-#[automatically_derived]
-impl ::core::cmp::PartialEq for TimeRange {
-    #[inline]
-    fn eq(&self, other: &TimeRange) -> bool {
-        self.0 == other.0
+    impl TryFrom<Vec<String>> for Fields {
+        type Error = &'static str;
+        fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
+            let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
+            Self::try_from(input.as_slice())
+        }
     }
-}
-impl<'de> serde::Deserialize<'de> for TimeRange {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_any(TimeRangeVisitor)
+    impl From<()> for Fields {
+        fn from(_: ()) -> Self {
+            Self::default()
+        }
     }
-}
-struct TimeRangeVisitor;
-impl<'de> serde::de::Visitor<'de> for TimeRangeVisitor {
-    type Value = TimeRange;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a single time string or an array with 1-2 time strings")
+    impl<'de> serde::Deserialize<'de> for Fields {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(FieldsVisitor)
+        }
     }
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        if value.is_empty() {
-            return Ok(TimeRange(None));
+    struct FieldsVisitor;
+    impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+        type Value = Fields;
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an array of strings matching the struct fields")
         }
-        match serde::Deserialize::deserialize(
-            serde::de::value::StrDeserializer::<E>::new(value),
-        ) {
-            Ok(start) => Ok(TimeRange(Some(start..=start))),
-            Err(err) => {
-                Err(
-                    E::custom(
-                        "invalid time format: ".to_string() + err.to_string().as_str(),
-                    ),
-                )
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut fields = Vec::new();
+            while let Some(field) = seq.next_element()? {
+                fields.push(field);
             }
+            Fields::try_from(fields).map_err(serde::de::Error::custom)
         }
-    }
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let start = match seq.next_element::<Option<DateTime<Utc>>>()? {
-            Some(Some(time)) => time,
-            Some(None) | None => return Ok(TimeRange(None)),
-        };
-        let end = match seq.next_element::<Option<DateTime<Utc>>>()? {
-            Some(Some(time)) => time,
-            Some(None) | None => start,
-        };
-        Ok(TimeRange(Some(start..=end)))
-    }
-    fn visit_unit<E>(self) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(TimeRange(None))
-    }
-}
-fn serde_compress<T>(items: Vec<T>) -> anyhow::Result<Vec<u8>>
-where
-    T: serde::Serialize,
-{
-    use std::io::Write;
-    let mut output = Vec::new();
-    let mut encoder = zstd::stream::write::Encoder::new(&mut output, 3)?;
-    for item in items {
-        rmp_serde::encode::write(&mut encoder, &item)?;
-    }
-    encoder.finish()?;
-    Ok(output)
-}
-fn serde_decompress<T>(input: &[u8]) -> impl Iterator<Item = anyhow::Result<T>> + '_
-where
-    T: for<'de> serde::Deserialize<'de> + 'static,
-{
-    let decoder = match zstd::stream::read::Decoder::new(input) {
-        Ok(d) => d,
-        Err(e) => {
-            return Box::new(std::iter::once(Err(e.into())))
-                as Box<dyn Iterator<Item = _>>;
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(Fields::default())
         }
-    };
-    let buffered = std::io::BufReader::with_capacity(128 * 1024, decoder);
-    let mut de = rmp_serde::decode::Deserializer::new(buffered);
-    Box::new(
-        std::iter::from_fn(move || match serde::Deserialize::deserialize(&mut de) {
-            Ok(item) => Some(Ok(item)),
-            Err(
-                rmp_serde::decode::Error::InvalidMarkerRead(ref e),
-            ) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
-            Err(e) => Some(Err(e.into())),
-        }),
-    )
-}
-fn pco_compress_nested<T>(nested_values: Vec<Vec<T>>) -> anyhow::Result<Vec<u8>>
-where
-    T: ::pco::data_types::Number,
-{
-    let mut lengths = Vec::new();
-    let mut values = Vec::new();
-    for vals in nested_values {
-        lengths.push(vals.len() as u64);
-        values.extend(vals);
     }
-    let length_bytes = ::pco::standalone::simple_compress(
-        &lengths,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let value_bytes = ::pco::standalone::simple_compress(
-        &values,
-        &::pco::ChunkConfig::default(),
-    )?;
-    let (length_bytes, value_bytes) = (
-        serde_bytes::Bytes::new(&length_bytes),
-        serde_bytes::Bytes::new(&value_bytes),
-    );
-    Ok(rmp_serde::to_vec(&(length_bytes, value_bytes))?)
-}
-fn pco_decompress_nested<T>(bytes: Vec<u8>) -> anyhow::Result<Vec<Vec<T>>>
-where
-    T: ::pco::data_types::Number,
-{
-    let (length_bytes, value_bytes): (Vec<u8>, Vec<u8>) = rmp_serde::from_slice(&bytes)?;
-    let lengths = ::pco::standalone::simple_decompress::<u64>(&length_bytes)?;
-    let values = ::pco::standalone::simple_decompress::<T>(&value_bytes)?;
-    let mut values = values.into_iter();
-    let mut nested_values = Vec::with_capacity(lengths.len());
-    for length in lengths {
-        nested_values.push(values.by_ref().take(length as usize).collect::<Vec<T>>());
-    }
-    Ok(nested_values)
-}
+};
@@ -12,18 +12,19 @@ pub struct QueryStat {
 
 #[tokio::test]
 #[serial_test::serial]
+#[allow(clippy::unnecessary_fallible_conversions)]
 async fn test() -> anyhow::Result<()> {
-    assert_eq!(Fields::default().select(), "database_id, granularity, collected_at, fingerprint");
-    assert_eq!(Fields::required().select(), "database_id, granularity, collected_at");
+    assert_eq!(QueryStatFields::default().select(), "database_id, granularity, collected_at, fingerprint");
+    assert_eq!(QueryStatFields::required().select(), "database_id, granularity, collected_at");
 
-    assert_eq!(Fields::default(), ().try_into().unwrap());
-    assert_eq!(Fields::required(), vec![].try_into().unwrap());
-    assert_eq!(Fields::default(), Fields::try_from(&["fingerprint"]).unwrap());
+    assert_eq!(QueryStatFields::default(), ().try_into().unwrap());
+    assert_eq!(QueryStatFields::required(), vec![].try_into().unwrap());
+    assert_eq!(QueryStatFields::default(), QueryStatFields::try_from(&["fingerprint"]).unwrap());
 
-    assert_eq!(Fields::default(), serde_json::from_str(r#"null"#)?);
-    assert_eq!(Fields::required(), serde_json::from_str(r#"[]"#)?);
-    assert_eq!(Fields::default(), serde_json::from_str(r#"["fingerprint"]"#)?);
-    assert!(serde_json::from_str::<Fields>(r#"["other"]"#).is_err());
+    assert_eq!(QueryStatFields::default(), serde_json::from_str(r#"null"#)?);
+    assert_eq!(QueryStatFields::required(), serde_json::from_str(r#"[]"#)?);
+    assert_eq!(QueryStatFields::default(), serde_json::from_str(r#"["fingerprint"]"#)?);
+    assert!(serde_json::from_str::<QueryStatFields>(r#"["other"]"#).is_err());
 
     let db = &super::DB_POOL.get().await?;
     let sql = "
@@ -41,27 +42,25 @@ async fn test() -> anyhow::Result<()> {
     db.batch_execute(sql).await?;
 
     let t = DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).context("out of range")?;
-    let filter = Filter::new(&[5], &[60], t..=t);
+    let filter = QueryStatFilter::new(&[5], &[60], t..=t);
     let s = QueryStat { database_id: 5, granularity: 60, collected_at: t, fingerprint: 0 };
-    let mut stats = Vec::new();
-    stats.push(QueryStat { fingerprint: 1, ..s });
-    stats.push(QueryStat { fingerprint: 2, ..s });
+    let stats = vec![QueryStat { fingerprint: 1, ..s }, QueryStat { fingerprint: 2, ..s }];
     CompressedQueryStats::store(db, stats.clone()).await?;
 
     // Including all fields
     let full = vec![QueryStat { fingerprint: 1, ..s }, QueryStat { fingerprint: 2, ..s }];
-    assert_eq!(full, load(db, filter.clone(), Fields::default()).await?);
-    assert_eq!(full, load(db, filter.clone(), Fields::new(&["fingerprint"])?).await?);
+    assert_eq!(full, load(db, filter.clone(), QueryStatFields::default()).await?);
+    assert_eq!(full, load(db, filter.clone(), QueryStatFields::new(&["fingerprint"])?).await?);
     assert_eq!(full, load(db, filter.clone(), ()).await?);
     assert_eq!(full, load(db, filter.clone(), &["fingerprint"]).await?);
 
     // Skipping some fields
     let partial = vec![QueryStat { fingerprint: 0, ..s }, QueryStat { fingerprint: 0, ..s }];
-    assert_eq!(partial, load(db, filter.clone(), Fields::new(&[])?).await?);
+    assert_eq!(partial, load(db, filter.clone(), QueryStatFields::new(&[])?).await?);
     assert_eq!(partial, load(db, filter.clone(), &[]).await?);
 
     // Error cases
-    assert!(Fields::new(&["other"]).is_err());
+    assert!(QueryStatFields::new(&["other"]).is_err());
     assert!(load(db, filter.clone(), &["other"]).await.is_err());
 
     // Optional filters are automatically included in the fields to be loaded
@@ -77,7 +76,7 @@ async fn test() -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn load(db: &deadpool_postgres::Client, filter: Filter, fields: impl TryInto<Fields>) -> anyhow::Result<Vec<QueryStat>> {
+async fn load(db: &deadpool_postgres::Client, filter: QueryStatFilter, fields: impl TryInto<QueryStatFields>) -> anyhow::Result<Vec<QueryStat>> {
     let mut rows = Vec::new();
     for group in CompressedQueryStats::load(db, filter, fields).await? {
         rows.extend(group.decompress()?);
@@ -86,7 +85,7 @@ async fn load(db: &deadpool_postgres::Client, filter: Filter, fields: impl TryIn
     Ok(rows)
 }
 
-async fn delete(db: &deadpool_postgres::Client, filter: Filter, fields: impl TryInto<Fields>) -> anyhow::Result<Vec<QueryStat>> {
+async fn delete(db: &deadpool_postgres::Client, filter: QueryStatFilter, fields: impl TryInto<QueryStatFields>) -> anyhow::Result<Vec<QueryStat>> {
     let mut rows = Vec::new();
     for group in CompressedQueryStats::delete(db, filter, fields).await? {
         rows.extend(group.decompress()?);
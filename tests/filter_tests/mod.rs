@@ -17,16 +17,16 @@ pub struct QueryStat {
 #[tokio::test]
 #[serial_test::serial]
 async fn test() -> anyhow::Result<()> {
-    let start = ymd_hms_micros(2026, 01, 01, 1, 01, 02, 345_678).unwrap();
-    let end = ymd_hms_micros(2026, 01, 01, 5, 03, 04, 567_890).unwrap();
-    let filter = Filter::new(&[5], &[60], start..=end);
-    assert_eq!(filter, Filter { database_id: vec![5], granularity: vec![60], collected_at: Some(start..=end), fingerprint: vec![] });
+    let start = ymd_hms_micros(2026, 1, 1, 1, 1, 2, 345_678).unwrap();
+    let end = ymd_hms_micros(2026, 1, 1, 5, 3, 4, 567_890).unwrap();
+    let filter = QueryStatFilter::new(&[5], &[60], start..=end);
+    assert_eq!(filter, QueryStatFilter { database_id: vec![5], granularity: vec![60], collected_at: Some(start..=end), fingerprint: vec![] });
 
     // Nanosecond precision is removed
     let s = start + Duration::nanoseconds(123);
     let e = end + Duration::nanoseconds(456);
     assert_ne!(start, s);
-    let mut filter = Filter::new(&[5], &[60], s..=e);
+    let mut filter = QueryStatFilter::new(&[5], &[60], s..=e);
     assert_eq!(filter.collected_at, Some(s..=e));
     filter.range_truncate()?;
     assert_eq!(filter.collected_at, Some(start..=end));
@@ -34,7 +34,7 @@ async fn test() -> anyhow::Result<()> {
     // Convenience functions
     let t = DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).context("out of range")?;
     let t2 = t + Duration::seconds(1);
-    let mut filter = Filter::new(&[], &[], t..=t2);
+    let mut filter = QueryStatFilter::new(&[], &[], t..=t2);
     assert_eq!(filter.range_duration()?, Duration::seconds(1));
     assert_eq!(filter.range_bounds()?, (t, t2));
     filter.range_shift(Duration::days(1))?;
@@ -43,18 +43,18 @@ async fn test() -> anyhow::Result<()> {
     assert_eq!(filter.range_bounds()?, (t - Duration::days(1), t2 - Duration::days(1)));
 
     // Deserialization
-    let filter: Filter =
+    let filter: QueryStatFilter =
         serde_json::from_str(r#"{"database_id": [1], "collected_at": ["2026-01-01T01:01:02.345678Z", "2026-01-01T05:03:04.567890Z"]}"#)?;
     assert_eq!(filter.database_id, vec![1]);
     assert_eq!(filter.collected_at, Some(start..=end));
-    let filter: Filter = serde_json::from_str(r#"{"database_id": 1, "collected_at": "2026-01-01T01:01:02.345678Z"}"#)?;
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"database_id": 1, "collected_at": "2026-01-01T01:01:02.345678Z"}"#)?;
     assert_eq!(filter.database_id, vec![1]);
     assert_eq!(filter.collected_at, Some(start..=start));
-    let filter: Filter = serde_json::from_str(r#"{"collected_at": ["2026-01-01T01:01:02.345678Z"]}"#)?;
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": ["2026-01-01T01:01:02.345678Z"]}"#)?;
     assert_eq!(filter.collected_at, Some(start..=start));
-    let filter: Filter = serde_json::from_str(r#"{"collected_at": []}"#)?;
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": []}"#)?;
     assert_eq!(filter.collected_at, None);
-    let filter: Filter = serde_json::from_str(r#"{"collected_at": null}"#)?;
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": null}"#)?;
     assert_eq!(filter.collected_at, None);
 
     let db = &super::DB_POOL.get().await?;
@@ -80,21 +80,21 @@ async fn test() -> anyhow::Result<()> {
     CompressedQueryStats::store(db, stats.clone()).await?;
 
     // Filtering by a single timestamp
-    let actual = load(db, Filter::new(&[5], &[60], t..=t)).await?;
+    let actual = load(db, QueryStatFilter::new(&[5], &[60], t..=t)).await?;
     assert_eq!(actual, vec![QueryStat { fingerprint: 1, ..s }, QueryStat { fingerprint: 2, ..s },]);
 
     // Filtering the whole time range
-    let actual = load(db, Filter::new(&[5], &[60], t..=t2)).await?;
+    let actual = load(db, QueryStatFilter::new(&[5], &[60], t..=t2)).await?;
     assert_eq!(actual, stats);
 
     // Optional filter
-    let mut filter = Filter::new(&[5], &[60], t..=t2);
+    let mut filter = QueryStatFilter::new(&[5], &[60], t..=t2);
     filter.fingerprint = vec![2];
     let actual = load(db, filter).await?;
     assert_eq!(actual, vec![QueryStat { fingerprint: 2, ..s }]);
 
     // Optional filter is not applied to deleted rows
-    let mut filter = Filter::new(&[5], &[60], t..=t2);
+    let mut filter = QueryStatFilter::new(&[5], &[60], t..=t2);
     filter.fingerprint = vec![2];
     let actual = delete(db, filter).await?;
     assert_eq!(actual, stats);
@@ -102,7 +102,7 @@ async fn test() -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn load(db: &deadpool_postgres::Client, filter: Filter) -> anyhow::Result<Vec<QueryStat>> {
+async fn load(db: &deadpool_postgres::Client, filter: QueryStatFilter) -> anyhow::Result<Vec<QueryStat>> {
     let mut rows = Vec::new();
     for group in CompressedQueryStats::load(db, filter, ()).await? {
         rows.extend(group.decompress()?);
@@ -111,7 +111,7 @@ async fn load(db: &deadpool_postgres::Client, filter: Filter) -> anyhow::Result<
     Ok(rows)
 }
 
-async fn delete(db: &deadpool_postgres::Client, filter: Filter) -> anyhow::Result<Vec<QueryStat>> {
+async fn delete(db: &deadpool_postgres::Client, filter: QueryStatFilter) -> anyhow::Result<Vec<QueryStat>> {
     let mut rows = Vec::new();
     for group in CompressedQueryStats::delete(db, filter, ()).await? {
         rows.extend(group.decompress()?);
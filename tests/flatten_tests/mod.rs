@@ -0,0 +1,160 @@
+use super::DB_POOL;
+use pco_store::PcoStore;
+use std::time::{Duration, SystemTime};
+
+#[pco_store::flatten]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IoStats {
+    pub read_time: f64,
+    pub write_time: f64,
+    pub blocks: i64,
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn flatten() -> anyhow::Result<()> {
+    #[pco_store::flatten]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Timing {
+        pub total: std::time::Duration,
+        pub calls: u8,
+    }
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], sort_by = [io_blocks])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        #[pco(flatten)]
+        pub io: IoStats,
+        #[pco(flatten)]
+        pub timing: Timing,
+        pub fingerprint: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            io_read_time bytea STORAGE EXTERNAL NOT NULL,
+            io_write_time bytea STORAGE EXTERNAL NOT NULL,
+            io_blocks bytea STORAGE EXTERNAL NOT NULL,
+            timing_total bytea STORAGE EXTERNAL NOT NULL,
+            timing_calls bytea STORAGE EXTERNAL NOT NULL,
+            fingerprint bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+    assert_eq!(
+        CompressedQueryStats::COLUMNS,
+        &[
+            "database_id",
+            "start_at",
+            "end_at",
+            "collected_at",
+            "io_read_time",
+            "io_write_time",
+            "io_blocks",
+            "timing_total",
+            "timing_calls",
+            "fingerprint"
+        ]
+    );
+
+    let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let stats = vec![
+        QueryStat {
+            database_id: 1,
+            collected_at: t,
+            io: IoStats { read_time: 1.5, write_time: 0.25, blocks: 10 },
+            timing: Timing { total: Duration::from_millis(30), calls: 3 },
+            fingerprint: 100,
+        },
+        QueryStat {
+            database_id: 1,
+            collected_at: t,
+            io: IoStats { read_time: 4.0, write_time: 0.0, blocks: 20 },
+            timing: Timing { total: Duration::from_millis(5), calls: 1 },
+            fingerprint: 200,
+        },
+    ];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    // Each field of a flattened struct is stored as its own pco column, and reassembled on decompress
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.io_blocks)?, vec![10, 20]);
+    assert_eq!(pco::standalone::simple_decompress::<f64>(&group.io_read_time)?, vec![1.5, 4.0]);
+    assert_eq!(group.decompress()?, stats);
+
+    // Flattened fields can be filtered
+    let mut filter = QueryStatFilter::new(&[1], t..=t);
+    filter.io_blocks = vec![20];
+    let group = CompressedQueryStats::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+
+    let mut filter = QueryStatFilter::new(&[1], t..=t);
+    filter.timing_total = Some(Duration::ZERO..=Duration::from_millis(10));
+    let group = CompressedQueryStats::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+
+    // Flattened fields can be loaded individually
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t..=t), &["io_read_time"]).await?.remove(0);
+    let io: Vec<IoStats> = group.decompress()?.into_iter().map(|s| s.io).collect();
+    assert_eq!(io, vec![IoStats { read_time: 1.5, ..Default::default() }, IoStats { read_time: 4.0, ..Default::default() }]);
+    Ok(())
+}
+
+mod wal {
+    #[pco_store::flatten]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct WalStats {
+        pub records: i64,
+        pub bytes: u64,
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn flatten_across_modules() -> anyhow::Result<()> {
+    use wal::WalStats;
+
+    // Flattened structs can be imported from another module, or named by their path
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        #[pco(flatten)]
+        pub wal: WalStats,
+        #[pco(flatten)]
+        pub previous_wal: wal::WalStats,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            wal_records bytea STORAGE EXTERNAL NOT NULL,
+            wal_bytes bytea STORAGE EXTERNAL NOT NULL,
+            previous_wal_records bytea STORAGE EXTERNAL NOT NULL,
+            previous_wal_bytes bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let stats = vec![
+        QueryStat { database_id: 1, wal: WalStats { records: 3, bytes: 100 }, previous_wal: WalStats { records: 1, bytes: 50 } },
+        QueryStat { database_id: 1, wal: WalStats { records: 5, bytes: 200 }, previous_wal: WalStats::default() },
+    ];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1]), ()).await?.remove(0);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.wal_records)?, vec![3, 5]);
+    assert_eq!(group.decompress()?, stats);
+    Ok(())
+}
@@ -0,0 +1,657 @@
+use super::DB_POOL;
+use half::f16;
+use std::time::SystemTime;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn number_types() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub small: u8,
+        pub signed_small: i8,
+        pub half: f16,
+        pub large: i128,
+        pub unsigned_large: u128,
+        pub smalls: Vec<u8>,
+        pub signed_smalls: Vec<i8>,
+        pub halves: Vec<f16>,
+        pub larges: Vec<i128>,
+        pub unsigned_larges: Vec<u128>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            small bytea STORAGE EXTERNAL NOT NULL,
+            signed_small bytea STORAGE EXTERNAL NOT NULL,
+            half bytea STORAGE EXTERNAL NOT NULL,
+            large bytea STORAGE EXTERNAL NOT NULL,
+            unsigned_large bytea STORAGE EXTERNAL NOT NULL,
+            smalls bytea STORAGE EXTERNAL NOT NULL,
+            signed_smalls bytea STORAGE EXTERNAL NOT NULL,
+            halves bytea STORAGE EXTERNAL NOT NULL,
+            larges bytea STORAGE EXTERNAL NOT NULL,
+            unsigned_larges bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let samples = vec![
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            small: u8::MAX,
+            signed_small: i8::MIN,
+            half: f16::from_f32(1.5),
+            large: i128::MIN,
+            unsigned_large: u128::MAX,
+            smalls: vec![0, 1, 255],
+            signed_smalls: vec![-128, 0, 127],
+            halves: vec![f16::from_f32(-0.25), f16::MAX],
+            larges: vec![-1, 0, i128::MAX],
+            unsigned_larges: vec![0, 1 << 64, u128::MAX],
+        },
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            small: 0,
+            signed_small: -1,
+            half: f16::from_f32(-2.0),
+            large: -(1 << 100),
+            unsigned_large: 12345,
+            smalls: vec![],
+            signed_smalls: vec![-1],
+            halves: vec![],
+            larges: vec![i128::MIN],
+            unsigned_larges: vec![],
+        },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, samples);
+
+    // Filtering by a 128-bit field
+    let mut filter = SampleFilter::new(&[1], t..=t);
+    filter.large = vec![-(1 << 100)];
+    let group = CompressedSamples::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![samples[1].clone()]);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Calls(i64);
+
+impl pco_store::PcoNumeric for Calls {
+    type Number = i64;
+
+    fn to_number(&self) -> i64 {
+        self.0
+    }
+
+    fn from_number(number: i64) -> Self {
+        Self(number)
+    }
+}
+
+type Bytes = u64;
+
+fn serde_compress<T: serde::Serialize>(items: Vec<T>) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 3)?;
+    for item in items {
+        rmp_serde::encode::write(&mut encoder, &item)?;
+    }
+    Ok(encoder.finish()?)
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn newtypes_and_aliases() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: Calls,
+        pub bytes: Bytes,
+        pub histogram: std::vec::Vec<Calls>,
+        pub flags: Vec<std::primitive::u8>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL,
+            bytes bytea STORAGE EXTERNAL NOT NULL,
+            histogram bytea STORAGE EXTERNAL NOT NULL,
+            flags bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let samples = vec![
+        Sample { database_id: 1, collected_at: t, calls: Calls(5), bytes: 1 << 40, histogram: vec![Calls(1), Calls(2)], flags: vec![0, 255] },
+        Sample { database_id: 1, collected_at: t, calls: Calls(-3), bytes: 0, histogram: vec![], flags: vec![1] },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+
+    // Newtypes implementing `PcoNumeric` and aliases of numbers are compressed with pco
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.calls)?, vec![5, -3]);
+    assert_eq!(pco::standalone::simple_decompress::<u64>(&group.bytes)?, vec![1 << 40, 0]);
+    assert_eq!(group.decompress()?, samples);
+
+    // Data written with MessagePack before they were compressed with pco can still be read
+    let sql = "UPDATE samples SET calls = $1";
+    db.execute(sql, &[&serde_compress(vec![Calls(7), Calls(8)])?]).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let calls: Vec<Calls> = group.decompress()?.into_iter().map(|s| s.calls).collect();
+    assert_eq!(calls, vec![Calls(7), Calls(8)]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn newtypes_without_timestamp() -> anyhow::Result<()> {
+    // Without a timestamp, the number of rows in a group comes from the newtype and alias fields
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub calls: Calls,
+        pub bytes: Bytes,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL,
+            bytes bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let samples = vec![Sample { database_id: 1, calls: Calls(5), bytes: 1 << 40 }, Sample { database_id: 1, calls: Calls(-3), bytes: 0 }];
+    CompressedSamples::store(db, samples.clone()).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1]), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, samples);
+
+    // Only loading one of the fields
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1]), &["calls"]).await?.remove(0);
+    let calls: Vec<Calls> = group.decompress()?.into_iter().map(|s| s.calls).collect();
+    assert_eq!(calls, vec![Calls(5), Calls(-3)]);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Ratio(f64);
+
+impl pco_store::PcoNumeric for Ratio {
+    type Number = f64;
+
+    fn to_number(&self) -> f64 {
+        self.0
+    }
+
+    fn from_number(number: f64) -> Self {
+        Self(number)
+    }
+}
+
+type Millis = f64;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn float_aliases() -> anyhow::Result<()> {
+    #[pco_store::store(group_by = [database_id], sort_by = [runtime], float_round = 2)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub runtime: Millis,
+        pub runtimes: Vec<Millis>,
+        pub ratio: Ratio,
+        pub calls: Calls,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            runtime bytea STORAGE EXTERNAL NOT NULL,
+            runtimes bytea STORAGE EXTERNAL NOT NULL,
+            ratio bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+    let sample = |database_id, runtime, runtimes, ratio| Sample { database_id, runtime, runtimes, ratio: Ratio(ratio), calls: Calls(3) };
+
+    // `float_round` applies to aliases and newtypes of floats the same way as to the floats themselves
+    let samples = vec![sample(1, 2.345678, vec![0.126], 0.5), sample(1, 1.004, vec![], 1.0 / 3.0)];
+    CompressedSamples::store(db, samples).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1]), ()).await?.remove(0);
+    assert_eq!(pco_store::__private::pco_number_type(&group.runtime)?, Some(pco::data_types::NumberType::I64));
+    assert_eq!(group.decompress()?, vec![sample(1, 1.0, vec![], 0.33), sample(1, 2.35, vec![0.13], 0.5)]);
+
+    // `sort_by` orders NaN values after all others, like it does for floats
+    CompressedSamples::store(db, vec![sample(2, f64::NAN, vec![], 1.0), sample(2, 3.0, vec![], 1.0), sample(2, -1.0, vec![], 1.0)]).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[2]), ()).await?.remove(0);
+    let runtimes: Vec<f64> = group.decompress()?.into_iter().map(|s| s.runtime).collect();
+    assert_eq!(runtimes[..2], [-1.0, 3.0]);
+    assert!(runtimes[2].is_nan());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn arrays_and_nested_vecs() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub buckets: [u64; 16],
+        pub bounds: [f64; 2],
+        pub histograms: Vec<Vec<u32>>,
+        pub calls: Vec<Vec<Calls>>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            buckets bytea STORAGE EXTERNAL NOT NULL,
+            bounds bytea STORAGE EXTERNAL NOT NULL,
+            histograms bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let samples = vec![
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            buckets: std::array::from_fn(|i| i as u64),
+            bounds: [0.5, 1.5],
+            histograms: vec![vec![1, 2, 3], vec![], vec![4]],
+            calls: vec![vec![Calls(1)], vec![Calls(-2), Calls(3)]],
+        },
+        Sample { database_id: 1, collected_at: t, buckets: [7; 16], bounds: [-1.0, f64::MAX], histograms: vec![], calls: vec![vec![]] },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+
+    // Arrays are transposed, so the values at each position are stored next to each other
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let buckets = pco::standalone::simple_decompress::<u64>(&group.buckets)?;
+    assert_eq!(&buckets[..4], &[0, 7, 1, 7]);
+    // Nested `Vec`s store their lengths and values with pco instead of MessagePack and zstd
+    assert!(!group.histograms.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]));
+    assert!(!group.calls.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]));
+    assert_eq!(group.decompress()?, samples);
+
+    // Data written with MessagePack before arrays and nested `Vec`s were compressed with pco can still be read
+    let sql = "UPDATE samples SET buckets = $1, bounds = $2, histograms = $3, calls = $4";
+    let buckets = serde_compress(vec![[1u64; 16], [2u64; 16]])?;
+    let bounds = serde_compress(vec![[1.0, 2.0], [3.0, 4.0]])?;
+    let histograms = serde_compress(vec![vec![vec![1u32]], vec![]])?;
+    let calls = serde_compress(vec![vec![vec![Calls(5)]], vec![vec![], vec![Calls(6)]]])?;
+    db.execute(sql, &[&buckets, &bounds, &histograms, &calls]).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let expected = vec![
+        Sample { database_id: 1, collected_at: t, buckets: [1; 16], bounds: [1.0, 2.0], histograms: vec![vec![1]], calls: vec![vec![Calls(5)]] },
+        Sample { database_id: 1, collected_at: t, buckets: [2; 16], bounds: [3.0, 4.0], histograms: vec![], calls: vec![vec![], vec![Calls(6)]] },
+    ];
+    assert_eq!(group.decompress()?, expected);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn arrays_without_timestamp() -> anyhow::Result<()> {
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub buckets: [u64; 2],
+        pub histograms: Vec<Vec<u32>>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            buckets bytea STORAGE EXTERNAL NOT NULL,
+            histograms bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let samples = vec![
+        Sample { database_id: 1, buckets: [1, 2], histograms: vec![vec![1, 2], vec![]] },
+        Sample { database_id: 1, buckets: [3, 4], histograms: vec![] },
+        Sample { database_id: 1, buckets: [5, 6], histograms: vec![vec![3]] },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1]), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, samples);
+
+    // Each field has the number of rows on its own
+    for field in ["buckets", "histograms"] {
+        let group = CompressedSamples::load(db, SampleFilter::new(&[1]), &[field]).await?.remove(0);
+        assert_eq!(group.decompress()?.len(), 3);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn nested_types() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub u8s: Vec<u8>,
+        pub u16s: Vec<u16>,
+        pub u32s: Vec<u32>,
+        pub u64s: Vec<u64>,
+        pub u128s: Vec<u128>,
+        pub i8s: Vec<i8>,
+        pub i16s: Vec<i16>,
+        pub i32s: Vec<i32>,
+        pub i64s: Vec<i64>,
+        pub i128s: Vec<i128>,
+        pub f16s: Vec<f16>,
+        pub f32s: Vec<f32>,
+        pub f64s: Vec<f64>,
+        pub bools: Vec<bool>,
+    }
+    let columns = ["u8s", "u16s", "u32s", "u64s", "u128s", "i8s", "i16s", "i32s", "i64s", "i128s", "f16s", "f32s", "f64s", "bools"];
+    let db = &DB_POOL.get().await?;
+    let columns_sql: Vec<_> = columns.iter().map(|c| format!("{c} bytea STORAGE EXTERNAL NOT NULL")).collect();
+    db.batch_execute(&format!(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            {}
+        );
+        ",
+        columns_sql.join(", ")
+    ))
+    .await?;
+
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let samples = vec![
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            u8s: vec![0, u8::MAX],
+            u16s: vec![1, u16::MAX],
+            u32s: vec![2, u32::MAX],
+            u64s: vec![3, u64::MAX],
+            u128s: vec![4, u128::MAX],
+            i8s: vec![i8::MIN, i8::MAX],
+            i16s: vec![i16::MIN, i16::MAX],
+            i32s: vec![i32::MIN, i32::MAX],
+            i64s: vec![i64::MIN, i64::MAX],
+            i128s: vec![i128::MIN, i128::MAX],
+            f16s: vec![f16::from_f32(0.5), f16::MIN],
+            f32s: vec![-1.25, f32::MAX],
+            f64s: vec![1e-300, f64::INFINITY],
+            bools: vec![true, false, true],
+        },
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            u8s: vec![],
+            u16s: vec![],
+            u32s: vec![],
+            u64s: vec![],
+            u128s: vec![],
+            i8s: vec![],
+            i16s: vec![],
+            i32s: vec![],
+            i64s: vec![],
+            i128s: vec![],
+            f16s: vec![],
+            f32s: vec![],
+            f64s: vec![],
+            bools: vec![],
+        },
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            u8s: vec![7],
+            u16s: vec![7],
+            u32s: vec![7],
+            u64s: vec![7],
+            u128s: vec![7],
+            i8s: vec![-7],
+            i16s: vec![-7],
+            i32s: vec![-7],
+            i64s: vec![-7],
+            i128s: vec![-7],
+            f16s: vec![f16::from_f32(-7.0)],
+            f32s: vec![-7.0],
+            f64s: vec![-7.0],
+            bools: vec![false],
+        },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, samples);
+
+    // Nested fields that aren't loaded are empty
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), &["bools"]).await?.remove(0);
+    let rows = group.decompress()?;
+    assert_eq!(rows.iter().map(|r| r.bools.clone()).collect::<Vec<_>>(), vec![vec![true, false, true], vec![], vec![false]]);
+    assert!(rows.iter().all(|r| r.u64s.is_empty() && r.f64s.is_empty() && r.i128s.is_empty()));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn maps() -> anyhow::Result<()> {
+    use std::collections::BTreeMap;
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub wait_events: BTreeMap<String, i64>,
+        pub percentiles: BTreeMap<u8, f64>,
+        pub calls: BTreeMap<String, Calls>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            wait_events bytea STORAGE EXTERNAL NOT NULL,
+            percentiles bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let map = |entries: &[(&str, i64)]| -> BTreeMap<String, i64> { entries.iter().map(|(k, v)| (k.to_string(), *v)).collect() };
+    let samples = vec![
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            wait_events: map(&[("ClientRead", 10), ("DataFileRead", 3)]),
+            percentiles: BTreeMap::from([(50, 1.5), (99, 20.25)]),
+            calls: BTreeMap::from([("select".to_string(), Calls(5))]),
+        },
+        Sample { database_id: 1, collected_at: t, wait_events: BTreeMap::new(), percentiles: BTreeMap::from([(50, 1.0)]), calls: BTreeMap::new() },
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            wait_events: map(&[("ClientRead", 7), ("Lock", -1)]),
+            percentiles: BTreeMap::new(),
+            calls: BTreeMap::from([("insert".to_string(), Calls(1)), ("select".to_string(), Calls(2))]),
+        },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+
+    // Keys are stored once in a dictionary, followed by the lengths, key indexes and values compressed with pco
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let (dictionary, lengths, keys, values): (serde_bytes::ByteBuf, serde_bytes::ByteBuf, serde_bytes::ByteBuf, serde_bytes::ByteBuf) =
+        rmp_serde::from_slice(&group.wait_events)?;
+    assert_eq!(dictionary.into_vec(), rmp_serde::to_vec(&("ClientRead", "DataFileRead", "Lock"))?[1..].to_vec());
+    assert_eq!(pco::standalone::simple_decompress::<u64>(&lengths)?, vec![2, 0, 2]);
+    assert_eq!(pco::standalone::simple_decompress::<u32>(&keys)?, vec![0, 1, 0, 2]);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&values)?, vec![10, 3, 7, -1]);
+    assert_eq!(group.decompress()?, samples);
+
+    // Maps can be filtered
+    let mut filter = SampleFilter::new(&[1], t..=t);
+    filter.wait_events = vec![BTreeMap::new()];
+    let group = CompressedSamples::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![samples[1].clone()]);
+
+    // Data written with MessagePack before maps were compressed with pco can still be read
+    let wait_events = serde_compress(vec![map(&[("Lock", 1)]), map(&[]), map(&[("Lock", 2)])])?;
+    let percentiles = serde_compress(vec![BTreeMap::from([(1u8, 0.5)]), BTreeMap::new(), BTreeMap::new()])?;
+    let calls = serde_compress(vec![BTreeMap::new(), BTreeMap::from([("a".to_string(), Calls(3))]), BTreeMap::new()])?;
+    let sql = "UPDATE samples SET wait_events = $1, percentiles = $2, calls = $3";
+    db.execute(sql, &[&wait_events, &percentiles, &calls]).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let expected = vec![
+        Sample { database_id: 1, collected_at: t, wait_events: map(&[("Lock", 1)]), percentiles: BTreeMap::from([(1, 0.5)]), calls: BTreeMap::new() },
+        Sample {
+            database_id: 1,
+            collected_at: t,
+            wait_events: BTreeMap::new(),
+            percentiles: BTreeMap::new(),
+            calls: BTreeMap::from([("a".to_string(), Calls(3))]),
+        },
+        Sample { database_id: 1, collected_at: t, wait_events: map(&[("Lock", 2)]), percentiles: BTreeMap::new(), calls: BTreeMap::new() },
+    ];
+    assert_eq!(group.decompress()?, expected);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn maps_without_timestamp() -> anyhow::Result<()> {
+    use std::collections::BTreeMap;
+
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub wait_events: BTreeMap<String, i64>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            wait_events bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    let samples = vec![
+        Sample { database_id: 1, wait_events: BTreeMap::from([("ClientRead".to_string(), 10)]) },
+        Sample { database_id: 1, wait_events: BTreeMap::new() },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1]), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, samples);
+    Ok(())
+}
+
+#[cfg(feature = "indexmap")]
+#[tokio::test]
+#[serial_test::serial]
+async fn index_maps() -> anyhow::Result<()> {
+    use indexmap::IndexMap;
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Sample {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub wait_events: IndexMap<String, u32>,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(
+        "
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            wait_events bytea STORAGE EXTERNAL NOT NULL
+        );
+        ",
+    )
+    .await?;
+
+    // Insertion order is kept
+    let t = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_225_600);
+    let samples = vec![
+        Sample { database_id: 1, collected_at: t, wait_events: IndexMap::from([("Lock".to_string(), 2), ("ClientRead".to_string(), 1)]) },
+        Sample { database_id: 1, collected_at: t, wait_events: IndexMap::from([("ClientRead".to_string(), 5)]) },
+    ];
+    CompressedSamples::store(db, samples.clone()).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert!(!group.wait_events.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]));
+    let decompressed = group.decompress()?;
+    assert_eq!(decompressed, samples);
+    assert_eq!(decompressed[0].wait_events.keys().collect::<Vec<_>>(), vec!["Lock", "ClientRead"]);
+
+    let wait_events = serde_compress(vec![IndexMap::from([("b".to_string(), 1u32), ("a".to_string(), 2)])])?;
+    db.execute("UPDATE samples SET wait_events = $1", &[&wait_events]).await?;
+    let group = CompressedSamples::load(db, SampleFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(group.decompress()?[0].wait_events.keys().collect::<Vec<_>>(), vec!["b", "a"]);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use super::DB_POOL;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::time::SystemTime;
+
+const SQL: &str = "
+    DROP TABLE IF EXISTS query_stats;
+    CREATE TABLE query_stats (
+        database_id bigint NOT NULL,
+        start_at timestamptz NOT NULL,
+        end_at timestamptz NOT NULL,
+        collected_at bytea STORAGE EXTERNAL NOT NULL,
+        calls bytea STORAGE EXTERNAL NOT NULL
+    );
+";
+
+fn ymd_hms_nanos(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32, nanos: u32) -> Option<DateTime<Utc>> {
+    Some(NaiveDate::from_ymd_opt(year, month, day)?.and_hms_nano_opt(hour, min, sec, nanos)?.and_utc())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn seconds() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], timestamp_precision = seconds)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 456_789_012).unwrap();
+    let truncated = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 0).unwrap();
+    CompressedQueryStats::store(db, vec![QueryStat { database_id: 1, collected_at: t, calls: 1 }]).await?;
+
+    // The stored timestamp and the `start_at` / `end_at` columns are truncated to seconds
+    let filter = QueryStatFilter::new(&[1], t..=t);
+    let group = CompressedQueryStats::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![QueryStat { database_id: 1, collected_at: truncated, calls: 1 }]);
+    let (start_at, end_at): (DateTime<Utc>, DateTime<Utc>) =
+        db.query_one("SELECT start_at, end_at FROM query_stats", &[]).await.map(|r| (r.get(0), r.get(1)))?;
+    assert_eq!((start_at, end_at), (truncated, truncated));
+
+    // Filters are truncated to seconds
+    let mut filter = QueryStatFilter::new(&[1], t..=t + Duration::milliseconds(100));
+    filter.range_truncate()?;
+    assert_eq!(filter.range_bounds()?, (truncated, truncated));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn nanos() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], timestamp_precision = nanos)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t1: SystemTime = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 456_789_012).unwrap().into();
+    let t2: SystemTime = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 456_789_345).unwrap().into();
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    // Nanoseconds are retained, and can be filtered on
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t2..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn before_epoch() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t1: SystemTime = ymd_hms_nanos(1969, 7, 20, 20, 17, 40, 123_456_000).unwrap().into();
+    let t2: SystemTime = ymd_hms_nanos(1970, 1, 1, 0, 0, 1, 0).unwrap().into();
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn legacy_unsigned_micros() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], timestamp_precision = millis)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    // Rows written before `timestamp_precision` was added stored `u64` microseconds
+    let t = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 456_789_000).unwrap();
+    let collected_at = pco::standalone::simple_compress(&[t.timestamp_micros() as u64], &pco::ChunkConfig::default())?;
+    let calls = pco::standalone::simple_compress(&[1i64], &pco::ChunkConfig::default())?;
+    let sql = "INSERT INTO query_stats VALUES (1, $1, $1, $2, $3)";
+    db.execute(sql, &[&t, &collected_at, &calls]).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t..=t + Duration::seconds(1)), ()).await?.remove(0);
+    let collected_at = ymd_hms_nanos(2026, 1, 1, 1, 2, 3, 456_000_000).unwrap();
+    assert_eq!(group.decompress()?, vec![QueryStat { database_id: 1, collected_at, calls: 1 }]);
+    Ok(())
+}
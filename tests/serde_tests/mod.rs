@@ -27,7 +27,7 @@ async fn string() -> anyhow::Result<()> {
             pub json: serde_json::Value,
             pub model: Option<Box<Model>>,
         }
-        async fn load(db: &deadpool_postgres::Client, filter: Filter) -> anyhow::Result<Vec<Model>> {
+        async fn load(db: &deadpool_postgres::Client, filter: ModelFilter) -> anyhow::Result<Vec<Model>> {
             let mut rows = Vec::new();
             for group in CompressedModels::load(db, filter, ()).await? {
                 rows.extend(group.decompress()?);
@@ -73,15 +73,15 @@ async fn string() -> anyhow::Result<()> {
         CompressedModels::store(db, data.clone()).await?;
 
         // Filtering by a single timestamp
-        let actual = load(db, Filter::new(&[id], &["a".into()], t..=t)).await?;
+        let actual = load(db, ModelFilter::new(&[id], &["a".into()], t..=t)).await?;
         assert_eq!(actual, vec![a1.clone()]);
 
         // Filtering the whole time range
-        let actual = load(db, Filter::new(&[id], &["a".into()], t..=t2)).await?;
+        let actual = load(db, ModelFilter::new(&[id], &["a".into()], t..=t2)).await?;
         assert_eq!(actual, data);
 
         // Filtering by compressed String field
-        let mut filter = Filter::new(&[id], &["a".into()], t..=t2);
+        let mut filter = ModelFilter::new(&[id], &["a".into()], t..=t2);
         filter.description = vec!["other".into()];
         let actual = load(db, filter).await?;
         assert_eq!(actual, vec![a2.clone()]);
@@ -104,7 +104,7 @@ async fn string() -> anyhow::Result<()> {
             #[serde(default)]
             pub new: bool,
         }
-        async fn load(db: &deadpool_postgres::Client, filter: Filter) -> anyhow::Result<Vec<Model>> {
+        async fn load(db: &deadpool_postgres::Client, filter: ModelFilter) -> anyhow::Result<Vec<Model>> {
             let mut rows = Vec::new();
             for group in CompressedModels::load(db, filter, ()).await? {
                 rows.extend(group.decompress()?);
@@ -119,15 +119,15 @@ async fn string() -> anyhow::Result<()> {
         CompressedModels::store(db, vec![a.clone()]).await?;
 
         // Filtering by a single timestamp
-        let actual = load(db, Filter::new(&[id], &["a".into()], t3..=t3)).await?;
+        let actual = load(db, ModelFilter::new(&[id], &["a".into()], t3..=t3)).await?;
         assert_eq!(actual, vec![a.clone()]);
 
         // Filtering the whole time range
-        let actual = load(db, Filter::new(&[id], &["a".into()], t..=t3)).await?;
+        let actual = load(db, ModelFilter::new(&[id], &["a".into()], t..=t3)).await?;
         assert_eq!(actual.len(), 3);
 
         // Filtering by new field
-        let mut filter = Filter::new(&[id], &["a".into()], t..=t3);
+        let mut filter = ModelFilter::new(&[id], &["a".into()], t..=t3);
         filter.new = vec![true];
         let actual = load(db, filter).await?;
         assert_eq!(actual, vec![a.clone()]);
@@ -135,3 +135,48 @@ async fn string() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[serial_test::serial]
+async fn without_timestamp() -> anyhow::Result<()> {
+    // MessagePack fields are read one row at a time, so without a timestamp or number field they give the row count
+    #[pco_store::store(group_by = [id])]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Model {
+        pub id: Uuid,
+        pub name: String,
+        pub tags: Vec<String>,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS models;
+        CREATE TABLE models (
+            id uuid NOT NULL,
+            name bytea STORAGE EXTERNAL NOT NULL,
+            tags bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+
+    let id = Uuid::default();
+    let data = vec![
+        Model { id, name: "a".into(), tags: vec!["x".into()] },
+        Model { id, name: "b".into(), tags: vec![] },
+        Model { id, name: "c".into(), tags: vec!["y".into(), "z".into()] },
+    ];
+    CompressedModels::store(db, data.clone()).await?;
+    let group = CompressedModels::load(db, ModelFilter::new(&[id]), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, data);
+
+    // Only loading one of the fields
+    let group = CompressedModels::load(db, ModelFilter::new(&[id]), &["tags"]).await?.remove(0);
+    let tags: Vec<_> = group.decompress()?.into_iter().map(|m| m.tags).collect();
+    assert_eq!(tags, vec![vec!["x".to_string()], vec![], vec!["y".into(), "z".into()]]);
+
+    // Filtering by a field that is read as the rows are built
+    let mut filter = ModelFilter::new(&[id]);
+    filter.name = vec!["b".into()];
+    let group = CompressedModels::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![data[1].clone()]);
+    Ok(())
+}
@@ -5,15 +5,28 @@ use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
 mod chrono_tests;
+#[cfg(feature = "rust_decimal")]
+mod decimal_tests;
 mod fields_tests;
 mod filter_tests;
+mod flatten_tests;
+mod number_tests;
+mod precision_tests;
 mod serde_tests;
+mod time_field_tests;
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+mod timestamp_type_tests;
 
 #[test]
 fn macrotest() {
     macrotest::expand("tests/expand/*.rs");
 }
 
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
+
 pub static DB_POOL: std::sync::LazyLock<std::sync::Arc<deadpool_postgres::Pool>> = std::sync::LazyLock::new(|| {
     if std::path::Path::new(".env").exists() {
         dotenvy::dotenv().unwrap();
@@ -67,7 +80,7 @@ async fn timestamp() {
 
     // Read
     let mut calls = 0;
-    let filter = Filter { database_id: vec![database_id], collected_at: Some(start..=end), ..Filter::default() };
+    let filter = QueryStatFilter { database_id: vec![database_id], collected_at: Some(start..=end), ..QueryStatFilter::default() };
     for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             calls += stat.calls;
@@ -78,7 +91,7 @@ async fn timestamp() {
     // Delete and re-group to improve compression
     assert_eq!(2, db.query_one("SELECT count(*) FROM query_stats", &[]).await.unwrap().get::<_, i64>(0));
     let mut stats = Vec::new();
-    let filter = Filter { database_id: vec![database_id], collected_at: Some(start..=end), ..Filter::default() };
+    let filter = QueryStatFilter { database_id: vec![database_id], collected_at: Some(start..=end), ..QueryStatFilter::default() };
     for group in CompressedQueryStats::delete(db, filter.clone(), ()).await.unwrap() {
         stats.extend(group.decompress().unwrap());
     }
@@ -108,7 +121,7 @@ async fn timestamp() {
     }
     let start = start + Duration::from_secs(3 * 60); // minute 3, skipping the first 2 minutes in the group
     let end = start + Duration::from_secs(23 * 60); // minute 26, skipping the last 4 minutes in the group
-    let filter = Filter { collected_at: Some(start..=end), ..filter };
+    let filter = QueryStatFilter { collected_at: Some(start..=end), ..filter };
     let groups = CompressedQueryStats::load(db, filter, ()).await.unwrap();
     assert_eq!(3, groups.len());
     let (mut calls, mut min, mut max) = (0, SystemTime::now(), SystemTime::UNIX_EPOCH);
@@ -139,7 +152,7 @@ async fn timestamp() {
         let end = end + Duration::from_secs(5 * 60); // minute 31
         let stat = QueryStat { database_id, collected_at: end, fingerprint: 1, calls: 1, total_time: 1.0, new_col: 1 };
         CompressedQueryStats::store(db, vec![stat]).await.unwrap();
-        let filter = Filter { database_id: vec![database_id], collected_at: Some(start..=end), ..Filter::default() };
+        let filter = QueryStatFilter { database_id: vec![database_id], collected_at: Some(start..=end), ..QueryStatFilter::default() };
         let groups = CompressedQueryStats::load(db, filter, ()).await.unwrap();
         assert_eq!(4, groups.len());
         let (mut calls, mut new_col, mut min, mut max) = (0, 0, SystemTime::now(), SystemTime::UNIX_EPOCH);
@@ -203,7 +216,7 @@ async fn aggregate() {
 
     // Read
     let mut calls = 0;
-    let filter = Filter::new(&[database_id], &[granularity], start..=end);
+    let filter = QueryStatFilter::new(&[database_id], &[granularity], start..=end);
     for group in CompressedQueryStats::load(db, filter.clone(), ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             calls += stat.calls;
@@ -216,7 +229,7 @@ async fn aggregate() {
     let mut stats: AHashMap<_, QueryStat> = AHashMap::new();
     let start: SystemTime = DateTime::<Utc>::from(end - Duration::from_secs(3600)).duration_trunc(chrono::Duration::hours(1)).unwrap().into();
     let end = start + Duration::from_secs(3600);
-    let filter = Filter { collected_at: Some(start..=end), ..filter };
+    let filter = QueryStatFilter { collected_at: Some(start..=end), ..filter };
     for group in CompressedQueryStats::load(db, filter.clone(), ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             match stats.entry((stat.database_id, stat.fingerprint)) {
@@ -237,7 +250,7 @@ async fn aggregate() {
     assert_eq!(2, db.query_one("SELECT count(*) FROM query_stats", &[]).await.unwrap().get::<_, i64>(0));
     CompressedQueryStats::store(db, stats).await.unwrap();
     assert_eq!(3, db.query_one("SELECT count(*) FROM query_stats", &[]).await.unwrap().get::<_, i64>(0));
-    let filter = Filter { granularity: vec![3600], ..filter };
+    let filter = QueryStatFilter { granularity: vec![3600], ..filter };
     let group = CompressedQueryStats::load(db, filter, ()).await.unwrap().remove(0);
     let stats = group.decompress().unwrap();
     assert_eq!(stats[0].collected_at, start);
@@ -274,13 +287,16 @@ async fn no_timestamp() {
 
     // Read
     let mut calls = 0;
-    let filter = Filter::new(&[database_id]);
+    let filter = QueryStatFilter::new(&[database_id]);
     for group in CompressedQueryStats::load(db, filter.clone(), ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             calls += stat.calls;
         }
     }
     assert_eq!(calls, 3);
+    let groups = CompressedQueryStats::load(db, filter.clone(), ()).await.unwrap();
+    let stats = CompressedQueryStats::decompress_all(groups).await.unwrap();
+    assert_eq!(stats.iter().map(|s| s.calls).sum::<i64>(), 3);
 
     // Delete and re-group
     assert_eq!(2, db.query_one("SELECT count(*) FROM query_stats", &[]).await.unwrap().get::<_, i64>(0));
@@ -328,7 +344,7 @@ async fn no_group_by() {
 
     // Read
     let mut calls = 0;
-    let filter = Filter::default();
+    let filter = QueryStatFilter::default();
     for group in CompressedQueryStats::load(db, filter.clone(), ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             calls += stat.calls;
@@ -382,7 +398,7 @@ async fn table_name() {
 
     // Read
     let mut calls = 0;
-    let filter = Filter::default();
+    let filter = QueryStatFilter::default();
     for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             calls += stat.calls;
@@ -420,7 +436,7 @@ async fn float_round() {
 
     // Read
     let mut total_time = 0.0;
-    let filter = Filter::new(&[database_id]);
+    let filter = QueryStatFilter::new(&[database_id]);
     for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
         for stat in group.decompress().unwrap() {
             total_time += stat.total_time;
@@ -456,7 +472,7 @@ async fn float_round() {
 
         // Read
         let mut total_time = 0.0;
-        let filter = Filter::new(&[database_id]);
+        let filter = QueryStatFilter::new(&[database_id]);
         for group in CompressedQueryStats::load(db, filter, ()).await.unwrap() {
             for stat in group.decompress().unwrap() {
                 total_time += stat.total_time;
@@ -467,6 +483,914 @@ async fn float_round() {
     }
 }
 
+#[tokio::test]
+#[serial_test::serial]
+async fn float_round_overflow() {
+    #[pco_store::store(group_by = [database_id], float_round = 2)]
+    #[derive(Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub total_time: f64,
+        pub mean_time: f32,
+    }
+    let db = &DB_POOL.get().await.unwrap();
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            total_time bytea STORAGE EXTERNAL NOT NULL,
+            mean_time bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await.unwrap();
+
+    // Groups with values that can't be rounded to an `i64` keep the original floats
+    let stats = vec![
+        QueryStat { database_id: 1, total_time: 1.2345, mean_time: 1.2345 },
+        QueryStat { database_id: 1, total_time: 1e300, mean_time: f32::INFINITY },
+        QueryStat { database_id: 1, total_time: f64::NEG_INFINITY, mean_time: -1e30 },
+    ];
+    let nan = vec![QueryStat { database_id: 2, total_time: f64::NAN, mean_time: 0.5 }];
+    let rounded = vec![QueryStat { database_id: 3, total_time: 9.876, mean_time: 1.2345 }];
+    CompressedQueryStats::store(db, stats).await.unwrap();
+    CompressedQueryStats::store(db, nan).await.unwrap();
+    CompressedQueryStats::store(db, rounded).await.unwrap();
+
+    let load = async |database_id| {
+        let mut group = CompressedQueryStats::load(db, QueryStatFilter::new(&[database_id]), ()).await.unwrap();
+        group.remove(0).decompress().unwrap()
+    };
+    let expected = vec![
+        QueryStat { database_id: 1, total_time: 1.2345, mean_time: 1.2345 },
+        QueryStat { database_id: 1, total_time: 1e300, mean_time: f32::INFINITY },
+        QueryStat { database_id: 1, total_time: f64::NEG_INFINITY, mean_time: -1e30 },
+    ];
+    assert_eq!(load(1).await, expected);
+    let nan = load(2).await;
+    assert!(nan[0].total_time.is_nan());
+    assert_eq!(nan[0].mean_time, 0.5);
+    assert_eq!(load(3).await, vec![QueryStat { database_id: 3, total_time: 9.88, mean_time: 1.23 }]);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn float_round_auto() {
+    #[pco_store::store(group_by = [database_id], float_round = auto)]
+    #[derive(Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub total_time: f64,
+        pub mean_time: f32,
+    }
+    let db = &DB_POOL.get().await.unwrap();
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            total_time bytea STORAGE EXTERNAL NOT NULL,
+            mean_time bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await.unwrap();
+
+    let decimals = vec![
+        QueryStat { database_id: 1, total_time: 12.34, mean_time: 0.5 },
+        QueryStat { database_id: 1, total_time: -0.001, mean_time: 3.0 },
+        QueryStat { database_id: 1, total_time: 100.0, mean_time: 1.25 },
+    ];
+    let fractions = vec![
+        QueryStat { database_id: 2, total_time: 1.0 / 3.0, mean_time: 2.0 / 3.0 },
+        QueryStat { database_id: 2, total_time: f64::NAN, mean_time: -0.0 },
+    ];
+    CompressedQueryStats::store(db, decimals).await.unwrap();
+    CompressedQueryStats::store(db, fractions).await.unwrap();
+
+    // The scale is chosen per group, and stored at the start of the payload
+    let scale = |bytes: &[u8]| rmp_serde::from_slice::<(Option<u8>, serde_bytes::ByteBuf)>(bytes).unwrap().0;
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1]), ()).await.unwrap().remove(0);
+    assert_eq!((scale(&group.total_time), scale(&group.mean_time)), (Some(3), Some(2)));
+    let expected = vec![
+        QueryStat { database_id: 1, total_time: 12.34, mean_time: 0.5 },
+        QueryStat { database_id: 1, total_time: -0.001, mean_time: 3.0 },
+        QueryStat { database_id: 1, total_time: 100.0, mean_time: 1.25 },
+    ];
+    assert_eq!(group.decompress().unwrap(), expected);
+
+    // Values that can't be represented exactly are stored as the original floats
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[2]), ()).await.unwrap().remove(0);
+    assert_eq!((scale(&group.total_time), scale(&group.mean_time)), (None, None));
+    let actual = group.decompress().unwrap();
+    assert_eq!((actual[0].total_time, actual[0].mean_time), (1.0 / 3.0, 2.0 / 3.0));
+    assert!(actual[1].total_time.is_nan());
+    assert!(actual[1].mean_time.is_sign_negative());
+
+    // Data written before `float_round = auto` was added can still be read
+    #[pco_store::store(group_by = [database_id], table_name = query_stats)]
+    pub struct QueryStatBefore {
+        pub database_id: i64,
+        pub total_time: f64,
+        pub mean_time: f32,
+    }
+    CompressedQueryStatBefores::store(db, vec![QueryStatBefore { database_id: 3, total_time: 1.5, mean_time: 0.1 }]).await.unwrap();
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[3]), ()).await.unwrap().remove(0);
+    assert_eq!(group.decompress().unwrap(), vec![QueryStat { database_id: 3, total_time: 1.5, mean_time: 0.1 }]);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn float_round_nested() {
+    #[pco_store::store(group_by = [database_id], float_round = 2)]
+    #[derive(Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub times: Vec<f64>,
+        pub means: Vec<f32>,
+    }
+    let db = &DB_POOL.get().await.unwrap();
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            times bytea STORAGE EXTERNAL NOT NULL,
+            means bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await.unwrap();
+
+    let rounded = vec![
+        QueryStat { database_id: 1, times: vec![1.2345, 9.876], means: vec![0.125] },
+        QueryStat { database_id: 1, times: vec![], means: vec![-1.23456, 2.0] },
+    ];
+    let overflow = vec![QueryStat { database_id: 2, times: vec![1.2345, f64::INFINITY], means: vec![1e30] }];
+    CompressedQueryStats::store(db, rounded).await.unwrap();
+    CompressedQueryStats::store(db, overflow).await.unwrap();
+
+    // Values in `Vec`s are rounded like single values, and stored as integers
+    let value_type = |bytes: &[u8]| {
+        let (_, values) = rmp_serde::from_slice::<(serde_bytes::ByteBuf, serde_bytes::ByteBuf)>(bytes).unwrap();
+        let (decompressor, rest) = pco::standalone::FileDecompressor::new(values.as_slice()).unwrap();
+        decompressor.peek_number_type_or_termination(rest).unwrap()
+    };
+    let load = async |database_id| CompressedQueryStats::load(db, QueryStatFilter::new(&[database_id]), ()).await.unwrap().remove(0);
+    let group = load(1).await;
+    assert_eq!(value_type(&group.times), Some(pco::data_types::NumberType::I64));
+    let expected = vec![
+        QueryStat { database_id: 1, times: vec![1.23, 9.88], means: vec![0.13] },
+        QueryStat { database_id: 1, times: vec![], means: vec![-1.23, 2.0] },
+    ];
+    assert_eq!(group.decompress().unwrap(), expected);
+
+    // Groups with values that can't be rounded keep the original floats
+    let group = load(2).await;
+    assert_eq!(value_type(&group.times), Some(pco::data_types::NumberType::F64));
+    let expected = vec![QueryStat { database_id: 2, times: vec![1.2345, f64::INFINITY], means: vec![1e30] }];
+    assert_eq!(group.decompress().unwrap(), expected);
+
+    // Groups written before `float_round` applied to `Vec` fields are still read
+    let nested = |values: &[f64]| {
+        let lengths = pco::standalone::simple_compress(&[values.len() as u64], &pco::ChunkConfig::default()).unwrap();
+        let values = pco::standalone::simple_compress(values, &pco::ChunkConfig::default()).unwrap();
+        rmp_serde::to_vec(&(serde_bytes::Bytes::new(&lengths), serde_bytes::Bytes::new(&values))).unwrap()
+    };
+    db.execute("UPDATE query_stats SET times = $1 WHERE database_id = 2", &[&nested(&[1.2345])]).await.unwrap();
+    assert_eq!(load(2).await.decompress().unwrap()[0].times, vec![1.2345]);
+    DB_POOL.manager().statement_caches.clear();
+    {
+        #[pco_store::store(group_by = [database_id], float_round = auto)]
+        #[derive(Debug, PartialEq)]
+        pub struct QueryStat {
+            pub database_id: i64,
+            pub times: Vec<f64>,
+            pub means: Vec<f32>,
+        }
+        let stats = vec![
+            QueryStat { database_id: 3, times: vec![12.34, -0.001], means: vec![0.5] },
+            QueryStat { database_id: 3, times: vec![100.0], means: vec![] },
+        ];
+        CompressedQueryStats::store(db, stats).await.unwrap();
+        let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[3]), ()).await.unwrap().remove(0);
+        // The scale is chosen for all values in the group
+        let (_, values) = rmp_serde::from_slice::<(serde_bytes::ByteBuf, serde_bytes::ByteBuf)>(&group.times).unwrap();
+        assert_eq!(rmp_serde::from_slice::<(Option<u8>, serde_bytes::ByteBuf)>(&values).unwrap().0, Some(3));
+        let expected = vec![
+            QueryStat { database_id: 3, times: vec![12.34, -0.001], means: vec![0.5] },
+            QueryStat { database_id: 3, times: vec![100.0], means: vec![] },
+        ];
+        assert_eq!(group.decompress().unwrap(), expected);
+
+        let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[2]), ()).await.unwrap().remove(0);
+        assert_eq!(group.decompress().unwrap()[0].times, vec![1.2345]);
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn lossless_floats() {
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub total_time: f64,
+    }
+    let db = &DB_POOL.get().await.unwrap();
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            total_time bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await.unwrap();
+
+    // Times in milliseconds with 2 decimal places, spread over several orders of magnitude
+    let mut state = 1u64;
+    let cents: Vec<i64> = (0..10_000)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % (1 << ((state >> 20) % 24))) as i64
+        })
+        .collect();
+    let stats: Vec<_> = cents.iter().map(|&c| QueryStat { database_id: 1, total_time: c as f64 / 100.0 }).collect();
+    CompressedQueryStats::store(db, stats).await.unwrap();
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1]), ()).await.unwrap().remove(0);
+
+    // pco detects the decimal multiplier by default, so without any option the floats compress to within 10% of
+    // the integers that benches/float/mult.rs stores, and less than half the size without multiplier detection
+    let total_time: Vec<f64> = cents.iter().map(|&c| c as f64 / 100.0).collect();
+    let classic = pco::ChunkConfig::default().with_mode_spec(pco::ModeSpec::Classic);
+    let classic = pco::standalone::simple_compress(&total_time, &classic).unwrap();
+    let mult = pco::standalone::simple_compress(&cents, &pco::ChunkConfig::default()).unwrap();
+    assert!(group.total_time.len() * 10 <= mult.len() * 11, "{} vs {}", group.total_time.len(), mult.len());
+    assert!(group.total_time.len() * 2 <= classic.len(), "{} vs {}", group.total_time.len(), classic.len());
+    assert_eq!(group.decompress().unwrap().into_iter().map(|s| s.total_time).collect::<Vec<_>>(), total_time);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn sort_by() {
+    let db = &DB_POOL.get().await.unwrap();
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            fingerprint bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await.unwrap();
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let end = start + Duration::from_secs(120);
+    let row =
+        |database_id: i64, minutes: u64, fingerprint: i64, calls: i64| (database_id, start + Duration::from_secs(minutes * 60), fingerprint, calls);
+    let unsorted = [row(1, 1, 2, 10), row(1, 0, 9, 20), row(1, 1, 1, 30), row(1, 0, 3, 40)];
+    let sorted = [row(1, 0, 3, 40), row(1, 0, 9, 20), row(1, 1, 1, 30), row(1, 1, 2, 10)];
+
+    // Data written without `sort_by` is kept in input order
+    {
+        #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+        pub struct QueryStat {
+            pub database_id: i64,
+            pub collected_at: SystemTime,
+            pub fingerprint: i64,
+            pub calls: i64,
+        }
+        let rows =
+            unsorted.iter().map(|&(database_id, collected_at, fingerprint, calls)| QueryStat { database_id, collected_at, fingerprint, calls });
+        CompressedQueryStats::store(db, rows).await.unwrap();
+        let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=end), ()).await.unwrap().remove(0);
+        let actual: Vec<_> = group.decompress().unwrap().into_iter().map(|r| (r.database_id, r.collected_at, r.fingerprint, r.calls)).collect();
+        assert_eq!(actual, unsorted);
+    }
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], sort_by = [collected_at, fingerprint])]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub fingerprint: i64,
+        pub calls: i64,
+    }
+    let to_tuples = |rows: Vec<QueryStat>| -> Vec<_> { rows.into_iter().map(|r| (r.database_id, r.collected_at, r.fingerprint, r.calls)).collect() };
+
+    // Groups written before `sort_by` was added are sorted when decompressed
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=end), ()).await.unwrap().remove(0);
+    assert_eq!(to_tuples(group.decompress().unwrap()), sorted);
+
+    // New groups are sorted before compression
+    db.batch_execute("TRUNCATE query_stats").await.unwrap();
+    let rows = unsorted.iter().map(|&(database_id, collected_at, fingerprint, calls)| QueryStat { database_id, collected_at, fingerprint, calls });
+    CompressedQueryStats::store(db, rows).await.unwrap();
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=end), ()).await.unwrap().remove(0);
+    let calls: Vec<i64> = pco::standalone::simple_decompress(&group.calls).unwrap();
+    assert_eq!(calls, vec![40, 20, 30, 10]);
+    assert_eq!(to_tuples(group.decompress().unwrap()), sorted);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn unique_by() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], unique_by = [collected_at, fingerprint])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub fingerprint: i64,
+        pub calls: i64,
+    }
+    let db = &mut DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            fingerprint bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL,
+            write_seq bigint GENERATED ALWAYS AS IDENTITY
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let end = start + Duration::from_secs(600);
+    let stat = |database_id, fingerprint, calls| QueryStat { database_id, collected_at: start, fingerprint, calls };
+
+    // Duplicates within a batch are dropped, keeping the last one
+    CompressedQueryStats::store(db, vec![stat(1, 1, 1), stat(1, 2, 1), stat(2, 1, 1), stat(1, 1, 2)]).await?;
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=end), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stat(1, 2, 1), stat(1, 1, 2)]);
+
+    // A retried batch creates an overlapping group, which a rewrite resolves in favor of the latest write
+    CompressedQueryStats::store(db, vec![stat(1, 1, 3), stat(1, 3, 3)]).await?;
+    let filter = QueryStatFilter::new(&[1, 2], start..=end);
+    CompressedQueryStats::rewrite(db, filter.clone()).await?;
+    let load = async |db: &deadpool_postgres::Object| -> anyhow::Result<Vec<QueryStat>> {
+        let mut stats = Vec::new();
+        for group in CompressedQueryStats::load(db, filter.clone(), ()).await? {
+            stats.extend(group.decompress()?);
+        }
+        stats.sort_by_key(|s| (s.database_id, s.fingerprint));
+        Ok(stats)
+    };
+    assert_eq!(load(db).await?, vec![stat(1, 1, 3), stat(1, 2, 1), stat(1, 3, 3), stat(2, 1, 1)]);
+    let groups = db.query_one("SELECT count(*) FROM query_stats", &[]).await?.get::<_, i64>(0);
+    assert_eq!(groups, 2);
+
+    // Groups written in the same transaction are still rewritten in the order they were written
+    db.batch_execute("BEGIN").await?;
+    CompressedQueryStats::store(db, vec![stat(1, 2, 4)]).await?;
+    CompressedQueryStats::store(db, vec![stat(1, 2, 5)]).await?;
+    db.batch_execute("COMMIT").await?;
+    CompressedQueryStats::rewrite(db, filter.clone()).await?;
+    assert_eq!(load(db).await?, vec![stat(1, 1, 3), stat(1, 2, 5), stat(1, 3, 3), stat(2, 1, 1)]);
+
+    // Tables without a write_seq column get an error explaining how to add it
+    db.batch_execute("ALTER TABLE query_stats DROP COLUMN write_seq").await?;
+    DB_POOL.manager().statement_caches.clear();
+    let message = "unique_by requires a write_seq column, add it with: \
+        ALTER TABLE query_stats ADD COLUMN write_seq bigint GENERATED ALWAYS AS IDENTITY";
+    let err = CompressedQueryStats::rewrite(db, filter.clone()).await.unwrap_err();
+    assert_eq!(err.to_string(), message);
+    let err = CompressedQueryStats::delete(db, filter.clone(), ()).await.err().unwrap();
+    assert_eq!(err.to_string(), message);
+    let err = CompressedQueryStats::store_merge(db, vec![stat(1, 1, 6)], Duration::from_secs(3600)).await.unwrap_err();
+    assert_eq!(err.to_string(), message);
+    assert_eq!(load(db).await?.len(), 4);
+    db.batch_execute("ALTER TABLE query_stats ADD COLUMN write_seq bigint GENERATED ALWAYS AS IDENTITY").await?;
+    DB_POOL.manager().statement_caches.clear();
+    CompressedQueryStats::rewrite(db, filter.clone()).await?;
+    assert_eq!(load(db).await?.len(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn store_merge() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &mut DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let count =
+        async |db: &deadpool_postgres::Object| -> anyhow::Result<i64> { Ok(db.query_one("SELECT count(*) FROM query_stats", &[]).await?.get(0)) };
+    let hour = Duration::from_secs(3600);
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let stat = |database_id, minutes: u64, calls| QueryStat { database_id, collected_at: start + Duration::from_secs(minutes * 60), calls };
+
+    // Rows in the same bucket are merged into a single group
+    CompressedQueryStats::store_merge(db, vec![stat(1, 0, 1), stat(2, 0, 1)], hour).await?;
+    CompressedQueryStats::store_merge(db, vec![stat(1, 10, 2), stat(1, 70, 3)], hour).await?;
+    CompressedQueryStats::store_merge(db, vec![stat(1, 20, 4)], hour).await?;
+    assert_eq!(count(db).await?, 3);
+    let groups = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=start + 2 * hour), ()).await?;
+    let mut groups: Vec<_> = groups.into_iter().map(|g| g.decompress()).collect::<anyhow::Result<_>>()?;
+    groups.sort_by_key(|g| g.len());
+    assert_eq!(groups, vec![vec![stat(1, 70, 3)], vec![stat(1, 0, 1), stat(1, 10, 2), stat(1, 20, 4)]]);
+
+    // Groups that span multiple buckets aren't changed
+    CompressedQueryStats::store(db, vec![stat(2, 50, 5), stat(2, 80, 5)]).await?;
+    CompressedQueryStats::store_merge(db, vec![stat(2, 55, 6)], hour).await?;
+    assert_eq!(count(db).await?, 4);
+    let groups = CompressedQueryStats::load(db, QueryStatFilter::new(&[2], start..=start + 2 * hour), ()).await?;
+    let mut groups: Vec<_> = groups.into_iter().map(|g| g.decompress()).collect::<anyhow::Result<_>>()?;
+    groups.sort_by_key(|g| g[0].collected_at);
+    assert_eq!(groups, vec![vec![stat(2, 0, 1), stat(2, 55, 6)], vec![stat(2, 50, 5), stat(2, 80, 5)]]);
+
+    // Failures are rolled back
+    assert!(CompressedQueryStats::store_merge(db, vec![stat(1, 30, 7)], Duration::ZERO).await.is_err());
+    db.batch_execute("ALTER TABLE query_stats ADD CONSTRAINT known_databases CHECK (database_id < 3)").await?;
+    assert!(CompressedQueryStats::store_merge(db, vec![stat(1, 30, 7), stat(3, 0, 7)], hour).await.is_err());
+    assert_eq!(count(db).await?, 4);
+
+    // Dropping the future part way through rolls back the transaction and releases its locks
+    db.batch_execute("ALTER TABLE query_stats DROP CONSTRAINT known_databases").await?;
+    let rows: Vec<_> = (0..500).map(|hours| stat(1, hours * 60, 8)).collect();
+    let _ = tokio::time::timeout(Duration::from_millis(5), CompressedQueryStats::store_merge(db, rows, hour)).await;
+    let pid: i32 = db.query_one("SELECT pg_backend_pid()", &[]).await?.get(0);
+    let state: String = DB_POOL.get().await?.query_one("SELECT state FROM pg_stat_activity WHERE pid = $1", &[&pid]).await?.get(0);
+    assert_eq!(state, "idle");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn max_group_size() -> anyhow::Result<()> {
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let end = start + Duration::from_secs(86_400);
+    let minute = |minutes: u64| start + Duration::from_secs(minutes * 60);
+    let bounds = async || -> anyhow::Result<Vec<(SystemTime, SystemTime)>> {
+        let rows = db.query("SELECT start_at, end_at FROM query_stats ORDER BY start_at", &[]).await?;
+        Ok(rows.into_iter().map(|r| (r.get(0), r.get(1))).collect())
+    };
+
+    // Groups are split by timestamp order into parts with at most `max_group_rows` rows
+    {
+        #[pco_store::store(timestamp = collected_at, group_by = [database_id], sort_by = [calls], max_group_rows = 2)]
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct QueryStat {
+            pub database_id: i64,
+            pub collected_at: SystemTime,
+            pub calls: i64,
+        }
+        db.batch_execute(sql).await?;
+        let stats: Vec<_> = [3, 0, 4, 1, 2].into_iter().map(|m| QueryStat { database_id: 1, collected_at: minute(m), calls: -(m as i64) }).collect();
+        CompressedQueryStats::store(db, stats).await?;
+        assert_eq!(bounds().await?, vec![(minute(0), minute(1)), (minute(2), minute(3)), (minute(4), minute(4))]);
+        let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], minute(2)..=minute(3)), ()).await?.remove(0);
+        let calls: Vec<_> = group.decompress()?.into_iter().map(|s| s.calls).collect();
+        assert_eq!(calls, vec![-3, -2]);
+    }
+
+    // Groups that compress larger than `max_group_bytes` are halved until they fit
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], max_group_bytes = 4000)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    db.batch_execute(sql).await?;
+    let mut stats = Vec::new();
+    let mut calls: i64 = 1;
+    for m in 0..1000 {
+        calls = calls.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        stats.push(QueryStat { database_id: 1, collected_at: minute(m), calls });
+    }
+    CompressedQueryStats::store(db, stats.clone()).await?;
+    let bounds = bounds().await?;
+    assert!(bounds.len() > 2);
+    assert!(bounds.windows(2).all(|b| b[0].1 < b[1].0));
+    let sizes = db.query("SELECT length(collected_at) + length(calls) FROM query_stats", &[]).await?;
+    assert!(sizes.iter().all(|r| r.get::<_, i32>(0) <= 4000));
+    let mut actual = Vec::new();
+    for group in CompressedQueryStats::load(db, QueryStatFilter::new(&[1], start..=end), ()).await? {
+        actual.extend(group.decompress()?);
+    }
+    actual.sort_by_key(|s| s.collected_at);
+    assert_eq!(actual, stats);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn store_stream() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let end = start + Duration::from_secs(3600);
+    let stat = |database_id, calls: i64| QueryStat { database_id, collected_at: start + Duration::from_secs(calls as u64), calls };
+    let load = async |database_id| -> anyhow::Result<Vec<Vec<QueryStat>>> {
+        let mut groups = Vec::new();
+        for group in CompressedQueryStats::load(db, QueryStatFilter::new(&[database_id], start..=end), ()).await? {
+            groups.push(group.decompress()?);
+        }
+        groups.sort_by_key(|g| g[0].calls);
+        Ok(groups)
+    };
+
+    // `store` accepts any iterator
+    CompressedQueryStats::store(db, (0..3).map(|calls| stat(1, calls))).await?;
+    CompressedQueryStats::store(db, std::iter::empty()).await?;
+    assert_eq!(load(1).await?, vec![vec![stat(1, 0), stat(1, 1), stat(1, 2)]]);
+
+    // Streamed groups are written as they fill up, with the remainder written at the end
+    let stats: Vec<_> = (10..17).map(|calls| stat(2, calls)).chain([stat(3, 20)]).collect();
+    CompressedQueryStats::store_stream(db, futures::stream::iter(stats), 3).await?;
+    let expected = vec![vec![stat(2, 10), stat(2, 11), stat(2, 12)], vec![stat(2, 13), stat(2, 14), stat(2, 15)], vec![stat(2, 16)]];
+    assert_eq!(load(2).await?, expected);
+    assert_eq!(load(3).await?, vec![vec![stat(3, 20)]]);
+    assert!(CompressedQueryStats::store_stream(db, futures::stream::empty(), 0).await.is_err());
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[tokio::test]
+#[serial_test::serial]
+async fn rayon() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], max_group_bytes = 2000, parallel = true)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+        pub total_time: f64,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL,
+            total_time bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let mut stats = Vec::new();
+    for database_id in 0..20 {
+        for i in 0..500 {
+            let calls = (i * i * 7919 + database_id) % 100_003;
+            stats.push(QueryStat { database_id, collected_at: start + Duration::from_secs(i as u64), calls, total_time: calls as f64 / 7.0 });
+        }
+    }
+
+    // Groups and fields are compressed on the rayon thread pool, including groups that are split afterwards
+    CompressedQueryStats::store(db, stats.clone()).await?;
+    let count: i64 = db.query_one("SELECT count(*) FROM query_stats", &[]).await?.get(0);
+    assert!(count > 20);
+    let ids: Vec<i64> = (0..20).collect();
+    let groups = CompressedQueryStats::load(db, QueryStatFilter::new(&ids, start..=start + Duration::from_secs(500)), ()).await?;
+    let mut actual = CompressedQueryStats::decompress_all(groups).await?;
+    actual.sort_by_key(|s| (s.database_id, s.collected_at));
+    assert_eq!(actual, stats);
+
+    // A panic while compressing is returned as an error instead of aborting the process
+    #[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+    pub struct Unserializable;
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            panic!("can't serialize")
+        }
+    }
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], table_name = query_stats, parallel = true)]
+    pub struct QueryStatPanic {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: Unserializable,
+    }
+    let stat = QueryStatPanic { database_id: 1, collected_at: start, calls: Unserializable };
+    let error = CompressedQueryStatPanics::store(db, vec![stat]).await.unwrap_err();
+    assert_eq!(error.to_string(), "rayon task panicked: can't serialize");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn decompress_all() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let end = start + Duration::from_secs(3600);
+    let mut stats = Vec::new();
+    for database_id in 0..40 {
+        for i in 0..10 {
+            stats.push(QueryStat { database_id, collected_at: start + Duration::from_secs(i * 60 + database_id as u64), calls: i as i64 });
+        }
+    }
+    CompressedQueryStats::store(db, stats.clone()).await?;
+    let ids: Vec<i64> = (0..40).collect();
+
+    // Data points are returned in group order
+    let groups = CompressedQueryStats::load(db, QueryStatFilter::new(&ids, start..=end), ()).await?;
+    let order: Vec<i64> = groups.iter().map(|g| g.database_id).collect();
+    let mut actual = CompressedQueryStats::decompress_all(groups).await?;
+    assert_eq!(actual.len(), stats.len());
+    let mut actual_order: Vec<i64> = actual.iter().map(|s| s.database_id).collect();
+    actual_order.dedup();
+    assert_eq!(actual_order, order);
+    actual.sort_by_key(|s| s.collected_at);
+    stats.sort_by_key(|s| s.collected_at);
+    assert_eq!(actual, stats);
+
+    // Or sorted by timestamp across groups
+    let groups = CompressedQueryStats::load(db, QueryStatFilter::new(&ids, start..=end), ()).await?;
+    assert_eq!(CompressedQueryStats::decompress_all_sorted(groups).await?, stats);
+    assert!(CompressedQueryStats::decompress_all_sorted(Vec::new()).await?.is_empty());
+
+    // Post-decompress filters still apply, including through the PcoStore trait
+    let mut filter = QueryStatFilter::new(&ids, start..=start + Duration::from_secs(120));
+    filter.calls = vec![1];
+    let groups = CompressedQueryStats::load(db, filter, ()).await?;
+    assert_eq!(pco_store::PcoStore::decompress_all(groups).await?.len(), 40);
+    assert!(CompressedQueryStats::decompress_all(Vec::new()).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn multiple_stores() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub runtime: Vec<f64>,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+        DROP TABLE IF EXISTS query_runs;
+        CREATE TABLE query_runs (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            runtime bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let stats = vec![QueryStat { database_id: 1, collected_at: t, calls: 3 }];
+    let runs = vec![QueryRun { database_id: 1, collected_at: t, runtime: vec![1.5, 2.5] }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+    CompressedQueryRuns::store(db, runs.clone()).await?;
+
+    // Each store has its own filter and fields types
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t..=t), QueryStatFields::default()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    let group = CompressedQueryRuns::load(db, QueryRunFilter::new(&[1], t..=t), QueryRunFields::default()).await?.remove(0);
+    assert_eq!(group.decompress()?, runs);
+    let filter: QueryRunFilter =
+        serde_json::from_str(r#"{"database_id": 1, "collected_at": [{"secs_since_epoch": 1767225600, "nanos_since_epoch": 0}]}"#)?;
+    assert_eq!(CompressedQueryRuns::load(db, filter, ()).await?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn pco_store_trait() -> anyhow::Result<()> {
+    use pco_store::PcoStore;
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    #[pco_store::store(group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub runtime: f64,
+    }
+
+    /// Generic compaction that rewrites all groups matching the filter as a single group
+    async fn compact<T: PcoStore>(db: &deadpool_postgres::Object, filter: T::Filter) -> anyhow::Result<usize> {
+        let mut rows = Vec::new();
+        for group in T::delete(db, filter, T::Fields::default()).await? {
+            rows.extend(group.decompress()?);
+        }
+        let len = rows.len();
+        T::store(db, rows).await?;
+        Ok(len)
+    }
+
+    assert_eq!(CompressedQueryStats::TABLE_NAME, "query_stats");
+    assert_eq!(CompressedQueryStats::COLUMNS, ["database_id", "start_at", "end_at", "collected_at", "calls"]);
+    assert_eq!(CompressedQueryStats::GROUP_BY, ["database_id"]);
+    assert_eq!(CompressedQueryStats::TIMESTAMP, Some("collected_at"));
+    assert_eq!(CompressedQueryRuns::COLUMNS, ["database_id", "runtime"]);
+    assert_eq!(CompressedQueryRuns::TIMESTAMP, None);
+
+    let db = DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+        DROP TABLE IF EXISTS query_runs;
+        CREATE TABLE query_runs (
+            database_id bigint NOT NULL,
+            runtime bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    for i in 0..3 {
+        let stat = QueryStat { database_id: 1, collected_at: t + Duration::from_secs(i * 60), calls: i as i64 };
+        CompressedQueryStats::store(&db, vec![stat]).await?;
+        CompressedQueryRuns::store(&db, vec![QueryRun { database_id: 1, runtime: i as f64 }]).await?;
+    }
+
+    let filter = QueryStatFilter::new(&[1], t..=t + Duration::from_secs(120));
+    assert_eq!(compact::<CompressedQueryStats>(&db, filter.clone()).await?, 3);
+    assert_eq!(CompressedQueryStats::load(&db, filter, ()).await?.len(), 1);
+
+    // The futures are `Send`, so generic jobs can be spawned
+    let filter = QueryRunFilter::new(&[1]);
+    let (db, len) = tokio::spawn(async move {
+        let len = compact::<CompressedQueryRuns>(&db, filter).await?;
+        anyhow::Ok((db, len))
+    })
+    .await??;
+    assert_eq!(len, 3);
+    let groups = CompressedQueryRuns::load(&db, QueryRunFilter::new(&[1]), ()).await?;
+    assert_eq!(groups.len(), 1);
+    let runtimes: Vec<f64> = PcoStore::decompress(groups.into_iter().next().unwrap())?.iter().map(|r| r.runtime).collect();
+    assert_eq!(runtimes, [0.0, 1.0, 2.0]);
+    Ok(())
+}
+
+#[test]
+#[allow(dead_code)]
+fn runtime_dependencies() {
+    // Generated code only refers to pco_store, so these names in the user's scope don't matter
+    mod ahash {}
+    mod anyhow {}
+    mod deadpool_postgres {}
+    mod futures {}
+    mod pco {}
+    mod rayon {}
+    mod rmp_serde {}
+    mod serde {}
+    mod serde_bytes {}
+    mod serde_with {}
+    mod tokio {}
+    mod tokio_postgres {}
+    mod zstd {}
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+        pub tags: Vec<String>,
+    }
+    let filter: QueryStatFilter =
+        serde_json::from_str(r#"{"database_id": 1, "collected_at": [{"secs_since_epoch": 0, "nanos_since_epoch": 0}]}"#).unwrap();
+    assert_eq!(filter.database_id, vec![1]);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn group_by_types() -> anyhow::Result<()> {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, postgres_types::ToSql, postgres_types::FromSql)]
+    #[postgres(transparent)]
+    pub struct DatabaseId(i64);
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id, shard, active, day, host, key])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        #[pco(sql_type = "int8")]
+        pub database_id: DatabaseId,
+        pub shard: i16,
+        pub active: bool,
+        pub day: chrono::NaiveDate,
+        pub host: std::net::IpAddr,
+        pub key: Vec<u8>,
+        pub collected_at: SystemTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    let sql = "
+        DROP TABLE IF EXISTS query_stats;
+        CREATE TABLE query_stats (
+            database_id bigint NOT NULL,
+            shard smallint NOT NULL,
+            active boolean NOT NULL,
+            day date NOT NULL,
+            host inet NOT NULL,
+            key bytea NOT NULL,
+            start_at timestamptz NOT NULL,
+            end_at timestamptz NOT NULL,
+            collected_at bytea STORAGE EXTERNAL NOT NULL,
+            calls bytea STORAGE EXTERNAL NOT NULL
+        );
+    ";
+    db.batch_execute(sql).await?;
+    let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);
+    let day = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    let host: std::net::IpAddr = "10.0.0.1".parse()?;
+    let stat = QueryStat { database_id: DatabaseId(1), shard: 2, active: true, day, host, key: vec![1, 2], collected_at: t, calls: 1 };
+    let stats = vec![stat.clone(), QueryStat { calls: 2, ..stat.clone() }, QueryStat { active: false, calls: 3, ..stat.clone() }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let filter = QueryStatFilter::new(&[DatabaseId(1)], &[2], &[true], &[day], &[host], &[vec![1, 2]], t..=t);
+    let groups = CompressedQueryStats::load(db, filter, ()).await?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].database_id, DatabaseId(1));
+    assert_eq!(groups.into_iter().next().unwrap().decompress()?, stats[..2]);
+
+    let filter = QueryStatFilter::new(&[DatabaseId(1)], &[2], &[true, false], &[day], &[host], &[vec![1, 2]], t..=t);
+    assert_eq!(CompressedQueryStats::load(db, filter, ()).await?.len(), 2);
+    let filter = QueryStatFilter::new(&[DatabaseId(2)], &[2], &[true], &[day], &[host], &[vec![1, 2]], t..=t);
+    assert!(CompressedQueryStats::load(db, filter, ()).await?.is_empty());
+    Ok(())
+}
+
 #[tokio::test]
 #[serial_test::serial]
 async fn boolean() {
@@ -494,7 +1418,7 @@ async fn boolean() {
     CompressedQueryStats::store(db, stats.clone()).await.unwrap();
 
     // Read
-    let filter = Filter::new(&[database_id]);
+    let filter = QueryStatFilter::new(&[database_id]);
     let group = CompressedQueryStats::load(db, filter, ()).await.unwrap().remove(0);
     assert_eq!(stats, group.decompress().unwrap());
 }
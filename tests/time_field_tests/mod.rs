@@ -0,0 +1,270 @@
+use super::DB_POOL;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::time::{Duration, SystemTime};
+
+const SQL: &str = "
+    DROP TABLE IF EXISTS query_runs;
+    CREATE TABLE query_runs (
+        database_id bigint NOT NULL,
+        start_at timestamptz NOT NULL,
+        end_at timestamptz NOT NULL,
+        collected_at bytea STORAGE EXTERNAL NOT NULL,
+        query_start bytea STORAGE EXTERNAL NOT NULL,
+        runtime bytea STORAGE EXTERNAL NOT NULL,
+        wait_time bytea STORAGE EXTERNAL NOT NULL
+    );
+";
+
+fn ymd_hms_micros(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32, micros: u32) -> Option<DateTime<Utc>> {
+    Some(NaiveDate::from_ymd_opt(year, month, day)?.and_hms_micro_opt(hour, min, sec, micros)?.and_utc())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn time_and_duration_fields() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub query_start: SystemTime,
+        pub runtime: std::time::Duration,
+        pub wait_time: chrono::Duration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = ymd_hms_micros(2026, 1, 1, 1, 2, 3, 456_789).unwrap();
+    let query_runs = vec![
+        QueryRun {
+            database_id: 1,
+            collected_at: t,
+            query_start: (t - chrono::Duration::seconds(5)).into(),
+            runtime: Duration::from_micros(1_500),
+            wait_time: chrono::Duration::microseconds(-250),
+        },
+        QueryRun {
+            database_id: 1,
+            collected_at: t,
+            query_start: ymd_hms_micros(1969, 12, 31, 23, 59, 59, 999_999).unwrap().into(),
+            runtime: Duration::from_secs(90),
+            wait_time: chrono::Duration::zero(),
+        },
+    ];
+    CompressedQueryRuns::store(db, query_runs.clone()).await?;
+
+    // Time and duration fields are compressed with pco
+    let group = CompressedQueryRuns::load(db, QueryRunFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert!(pco::standalone::simple_decompress::<i64>(&group.runtime).is_ok());
+    assert!(pco::standalone::simple_decompress::<i64>(&group.query_start).is_ok());
+    assert_eq!(group.decompress()?, query_runs);
+
+    // Time and duration fields can be filtered by range
+    let mut filter = QueryRunFilter::new(&[1], t..=t);
+    filter.runtime = Some(Duration::from_secs(1)..=Duration::from_secs(120));
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![query_runs[1].clone()]);
+
+    let mut filter = QueryRunFilter::new(&[1], t..=t);
+    filter.query_start = Some((t - chrono::Duration::hours(1)).into()..=t.into());
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![query_runs[0].clone()]);
+
+    let filter: QueryRunFilter = serde_json::from_str(
+        r#"{"database_id": 1, "collected_at": "2026-01-01T01:02:03.456789Z", "runtime": [{"secs": 0, "nanos": 0}, {"secs": 0, "nanos": 2000000}]}"#,
+    )?;
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![query_runs[0].clone()]);
+
+    // Fields are loaded when used in a filter
+    let mut filter = QueryRunFilter::new(&[1], t..=t);
+    filter.wait_time = Some(chrono::Duration::zero()..=chrono::Duration::zero());
+    let group = CompressedQueryRuns::load(db, filter, QueryRunFields::required()).await?.remove(0);
+    assert_eq!(group.decompress()?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn duration_precision() -> anyhow::Result<()> {
+    // Durations are stored as microseconds, regardless of `timestamp_precision`
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id], timestamp_precision = seconds)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub query_start: SystemTime,
+        pub runtime: std::time::Duration,
+        pub wait_time: chrono::Duration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = ymd_hms_micros(2026, 1, 1, 1, 2, 3, 0).unwrap();
+    let run = QueryRun {
+        database_id: 1,
+        collected_at: t,
+        query_start: t.into(),
+        runtime: Duration::from_micros(1_500),
+        wait_time: chrono::Duration::microseconds(-250),
+    };
+    CompressedQueryRuns::store(db, vec![run.clone()]).await?;
+
+    let group = CompressedQueryRuns::load(db, QueryRunFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.runtime)?, vec![1_500]);
+    assert_eq!(pco::standalone::simple_decompress::<i64>(&group.wait_time)?, vec![-250]);
+    assert_eq!(group.decompress()?, vec![run.clone()]);
+
+    let mut filter = QueryRunFilter::new(&[1], t..=t);
+    filter.runtime = Some(Duration::from_millis(1)..=Duration::from_millis(2));
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![run]);
+    Ok(())
+}
+
+fn serde_compress<T: serde::Serialize>(items: Vec<T>) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 3)?;
+    for item in items {
+        rmp_serde::encode::write(&mut encoder, &item)?;
+    }
+    Ok(encoder.finish()?)
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn legacy_serde_fields() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub query_start: DateTime<Utc>,
+        pub runtime: std::time::Duration,
+        pub wait_time: chrono::Duration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    // Time and duration fields other than `timestamp` used to be stored with MessagePack and zstd
+    let t = ymd_hms_micros(2026, 1, 1, 1, 2, 3, 456_789).unwrap();
+    let collected_at = pco::standalone::simple_compress(&[t.timestamp_micros()], &pco::ChunkConfig::default())?;
+    let query_start = serde_compress(vec![t - chrono::Duration::seconds(5)])?;
+    let runtime = serde_compress(vec![Duration::from_millis(1_500)])?;
+    let wait_time = serde_compress(vec![chrono::Duration::milliseconds(3)])?;
+    let sql = "INSERT INTO query_runs VALUES (1, $1, $1, $2, $3, $4, $5)";
+    db.execute(sql, &[&t, &collected_at, &query_start, &runtime, &wait_time]).await?;
+
+    let group = CompressedQueryRuns::load(db, QueryRunFilter::new(&[1], t..=t), ()).await?.remove(0);
+    let expected = QueryRun {
+        database_id: 1,
+        collected_at: t,
+        query_start: t - chrono::Duration::seconds(5),
+        runtime: Duration::from_millis(1_500),
+        wait_time: chrono::Duration::milliseconds(3),
+    };
+    assert_eq!(group.decompress()?, vec![expected]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn ambiguous_types() -> anyhow::Result<()> {
+    use time::Duration;
+
+    // `jiff::civil::DateTime` isn't mistaken for `chrono::DateTime`. `Duration` must be written with its crate.
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: DateTime<Utc>,
+        pub query_start: jiff::civil::DateTime,
+        pub runtime: time::Duration,
+        pub wait_time: time::Duration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = ymd_hms_micros(2026, 1, 1, 1, 2, 3, 456_789).unwrap();
+    let run = QueryRun {
+        database_id: 1,
+        collected_at: t,
+        query_start: jiff::civil::date(2026, 1, 1).at(1, 1, 58, 0),
+        runtime: Duration::microseconds(1_500),
+        wait_time: Duration::microseconds(-250),
+    };
+    CompressedQueryRuns::store(db, vec![run.clone()]).await?;
+
+    // `query_start` is stored with MessagePack
+    let group = CompressedQueryRuns::load(db, QueryRunFilter::new(&[1], t..=t), ()).await?.remove(0);
+    assert!(pco::standalone::simple_decompress::<i64>(&group.query_start).is_err());
+    assert_eq!(group.decompress()?, vec![run]);
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[tokio::test]
+#[serial_test::serial]
+async fn time_duration() -> anyhow::Result<()> {
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: time::OffsetDateTime,
+        pub query_start: time::OffsetDateTime,
+        pub runtime: time::Duration,
+        pub wait_time: time::Duration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t = time::macros::datetime!(2026-01-01 01:02:03.456789 UTC);
+    let run = QueryRun {
+        database_id: 1,
+        collected_at: t,
+        query_start: t - time::Duration::seconds(5),
+        runtime: time::Duration::microseconds(1_500),
+        wait_time: time::Duration::microseconds(-250),
+    };
+    CompressedQueryRuns::store(db, vec![run.clone()]).await?;
+
+    let filter: QueryRunFilter =
+        serde_json::from_str(r#"{"database_id": 1, "collected_at": "2026-01-01T01:02:03.456789Z", "query_start": "2026-01-01T01:01:58.456789Z"}"#)?;
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![run]);
+    Ok(())
+}
+
+#[cfg(feature = "jiff")]
+#[tokio::test]
+#[serial_test::serial]
+async fn jiff_duration() -> anyhow::Result<()> {
+    use jiff::{SignedDuration, Timestamp};
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryRun {
+        pub database_id: i64,
+        pub collected_at: Timestamp,
+        pub query_start: jiff::Timestamp,
+        pub runtime: SignedDuration,
+        pub wait_time: SignedDuration,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t: Timestamp = "2026-01-01T01:02:03.456789Z".parse()?;
+    let run = QueryRun {
+        database_id: 1,
+        collected_at: t,
+        query_start: t - SignedDuration::from_secs(5),
+        runtime: SignedDuration::from_micros(1_500),
+        wait_time: SignedDuration::from_micros(-250),
+    };
+    CompressedQueryRuns::store(db, vec![run.clone()]).await?;
+
+    let mut filter = QueryRunFilter::new(&[1], t..=t);
+    filter.wait_time = Some(SignedDuration::from_micros(-250)..=SignedDuration::ZERO);
+    let group = CompressedQueryRuns::load(db, filter, ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![run]);
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use super::DB_POOL;
+
+const SQL: &str = "
+    DROP TABLE IF EXISTS query_stats;
+    CREATE TABLE query_stats (
+        database_id bigint NOT NULL,
+        start_at timestamptz NOT NULL,
+        end_at timestamptz NOT NULL,
+        collected_at bytea STORAGE EXTERNAL NOT NULL,
+        calls bytea STORAGE EXTERNAL NOT NULL
+    );
+";
+
+#[cfg(feature = "time")]
+#[tokio::test]
+#[serial_test::serial]
+async fn time() -> anyhow::Result<()> {
+    use time::OffsetDateTime;
+    use time::macros::datetime;
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: OffsetDateTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t1 = datetime!(2026-01-01 01:02:03.456789 UTC);
+    let t2 = datetime!(2026-01-01 02:03:04.567891 UTC);
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t2..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+
+    // Filter helpers
+    let mut filter = QueryStatFilter::new(&[1], t1..=t2);
+    assert_eq!(filter.range_duration()?, t2 - t1);
+    filter.range_shift(time::Duration::days(1))?;
+    assert_eq!(filter.range_bounds()?, (t1 + time::Duration::days(1), t2 + time::Duration::days(1)));
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": ["2026-01-01T01:02:03.456789Z", "2026-01-01T02:03:04.567891Z"]}"#)?;
+    assert_eq!(filter.collected_at, Some(t1..=t2));
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": "2026-01-01T01:02:03.456789Z"}"#)?;
+    assert_eq!(filter.collected_at, Some(t1..=t1));
+    Ok(())
+}
+
+#[cfg(feature = "jiff")]
+#[tokio::test]
+#[serial_test::serial]
+async fn jiff() -> anyhow::Result<()> {
+    use jiff::{SignedDuration, Timestamp};
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: Timestamp,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t1: Timestamp = "2026-01-01T01:02:03.456789Z".parse()?;
+    let t2: Timestamp = "2026-01-01T02:03:04.567891Z".parse()?;
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t2..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+
+    // Filter helpers
+    let mut filter = QueryStatFilter::new(&[1], t1..=t2);
+    assert_eq!(filter.range_duration()?, t2.duration_since(t1));
+    filter.range_shift(SignedDuration::from_hours(24))?;
+    assert_eq!(filter.range_bounds()?, (t1 + SignedDuration::from_hours(24), t2 + SignedDuration::from_hours(24)));
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": ["2026-01-01T01:02:03.456789Z", "2026-01-01T02:03:04.567891Z"]}"#)?;
+    assert_eq!(filter.collected_at, Some(t1..=t2));
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+#[serial_test::serial]
+async fn chrono_naive() -> anyhow::Result<()> {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: NaiveDateTime,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    let t1 = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_hms_micro_opt(1, 2, 3, 456_789).unwrap();
+    let t2 = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_hms_micro_opt(2, 3, 4, 567_891).unwrap();
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, stats);
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t2..=t2), ()).await?.remove(0);
+    assert_eq!(group.decompress()?, vec![stats[1].clone()]);
+    assert_eq!(QueryStatFilter::new(&[1], t1..=t2).range_duration()?, t2 - t1);
+    let filter: QueryStatFilter = serde_json::from_str(r#"{"collected_at": "2026-01-01T01:02:03.456789"}"#)?;
+    assert_eq!(filter.collected_at, Some(t1..=t1));
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+#[serial_test::serial]
+async fn chrono_fixed_offset() -> anyhow::Result<()> {
+    use chrono::{DateTime, FixedOffset};
+
+    #[pco_store::store(timestamp = collected_at, group_by = [database_id])]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct QueryStat {
+        pub database_id: i64,
+        pub collected_at: DateTime<FixedOffset>,
+        pub calls: i64,
+    }
+    let db = &DB_POOL.get().await?;
+    db.batch_execute(SQL).await?;
+
+    // Offsets aren't stored, so timestamps are returned in UTC
+    let t1 = DateTime::parse_from_rfc3339("2026-01-01T03:02:03.456789+02:00")?;
+    let t2 = DateTime::parse_from_rfc3339("2026-01-01T02:03:04.567891Z")?;
+    let stats = vec![QueryStat { database_id: 1, collected_at: t1, calls: 1 }, QueryStat { database_id: 1, collected_at: t2, calls: 2 }];
+    CompressedQueryStats::store(db, stats.clone()).await?;
+
+    let group = CompressedQueryStats::load(db, QueryStatFilter::new(&[1], t1..=t2), ()).await?.remove(0);
+    let actual = group.decompress()?;
+    assert_eq!(actual, stats);
+    assert_eq!(actual[0].collected_at.offset().local_minus_utc(), 0);
+    Ok(())
+}